@@ -1,25 +1,61 @@
+use std::collections::HashMap;
 use std::default::Default;
 use std::env;
 use std::ffi::OsString;
 use std::fs::File;
-use std::io::prelude::*;
-use std::path::Path;
-use std::time::Duration;
+use std::io::{self, Read};
+use std::num::ParseFloatError;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use clap::ArgMatches;
+use termion::color;
+#[cfg(feature = "toml")]
+use toml;
 
-use game::View;
-use AppResult;
+use game::{TerminationCondition, View, AGE_CHARS, DEFAULT_HISTORY_LEN};
+use grid::{Grid, Neighborhood, Point};
+use rule::Rule;
+use {AppError, AppResult};
 
 static SAMPLE_DIR: &str = "./sample_patterns";
-static SAMPLE_CHOICES: &[&str] = &["beacon", "glider", "blinker", "toad"];
-static VIEW_CHOICES: &[&str] = &["centered", "fixed", "follow"];
+#[cfg(feature = "toml")]
+static CONFIG_FILE: &str = "conway.toml";
+static DEFAULT_SAMPLE: &str = "glider";
+/// The line separating frames written to a file.
+pub static FRAME_RULE: &str = "%";
+static SAMPLE_CHOICES: &[&str] = &["beacon", "eater", "glider", "gosper_gun", "blinker", "toad"];
+static PRESET_CHOICES: &[&str] = &["gun_demo", "oscillators"];
+/// Presets are scenes made of sample patterns, each placed at an offset.
+static PRESETS: &[(&str, &[(&str, Point)])] = &[
+    (
+        "gun_demo",
+        &[("gosper_gun", Point(0, 0)), ("eater", Point(39, 25))],
+    ),
+    (
+        "oscillators",
+        &[
+            ("blinker", Point(0, 0)),
+            ("toad", Point(8, 0)),
+            ("beacon", Point(16, 0)),
+        ],
+    ),
+];
+static VIEW_CHOICES: &[&str] = &["centered", "fixed", "follow", "locked"];
+static NEIGHBORHOOD_CHOICES: &[&str] = &["moore", "von-neumann", "diagonal"];
+static COLOR_CHOICES: &[&str] = &[
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+static DEFAULT_CHAR_TRAIL: &str = ".";
+pub const CHAR_TRAIL: char = '.';
 
 lazy_static! {
     static ref DEFAULT_CHAR_ALIVE: &'static str = "#";
     static ref DEFAULT_CHAR_DEAD: &'static str = "-";
     pub static ref CHAR_ALIVE: char = DEFAULT_CHAR_ALIVE.parse().unwrap();
     pub static ref CHAR_DEAD: char = DEFAULT_CHAR_DEAD.parse().unwrap();
+    static ref DEFAULT_HISTORY_LEN_ARG: String = DEFAULT_HISTORY_LEN.to_string();
 }
 
 fn parse_args<'a, I, T>(args: I) -> ArgMatches<'a>
@@ -31,22 +67,85 @@ where
         (version: "0.1")
         (author: "Dustin Rohde <dustin.rohde@gmail.com>")
         (about: "A shell utility for running Conway's Game of Life simulations.")
-        (@group source +required =>
+        (@group source =>
             (@arg file: -F --file display_order(1)
                 +takes_value
-                "load a pattern from a file")
+                "load a pattern from a file, or from stdin if FILE is -")
             (@arg sample: -S --sample display_order(1)
-                default_value[glider]
+                +takes_value
                 possible_values(SAMPLE_CHOICES)
-                "load a sample pattern")
+                "load a sample pattern [default: glider]")
+            (@arg random: --random display_order(1)
+                +takes_value
+                "fill a WIDTHxHEIGHT region with random cells")
+            (@arg preset: --preset display_order(1)
+                +takes_value
+                possible_values(PRESET_CHOICES)
+                "load a scene made of several sample patterns")
+            (@arg pattern: --pattern display_order(1)
+                +takes_value
+                "use this pattern, with rows separated by newlines or \\n")
         )
+        (@arg density: --density display_order(1)
+            +takes_value
+            requires[random]
+            "probability of each random cell being alive [default: 0.5]")
+        (@arg seed: --seed display_order(1)
+            +takes_value
+            requires[random]
+            "seed for generating random cells [default: random]")
         (@arg delay: -d --delay display_order(2)
             default_value("500")
             "delay (ms) between ticks")
+        (@arg gps: --gps alias[fps] display_order(2)
+            +takes_value
+            "generations per second (at most 1000), in place of --delay")
+        (@arg rule: --rule display_order(2)
+            default_value("B3/S23")
+            "rule deciding which cells are born and survive, in B/S notation")
+        (@arg stop_when_stable: --("stop-when-stable") display_order(2)
+            "end the game once the pattern stops changing")
+        (@arg max_generations: --("max-gens") display_order(2)
+            +takes_value
+            "end the game after this many generations [default: unlimited]")
+        (@arg history_len: --("history-len") display_order(2)
+            default_value(&DEFAULT_HISTORY_LEN_ARG)
+            "number of previous generations to remember, each a full copy of the pattern")
+        (@arg interactive: -i --interactive display_order(3)
+            "run in an interactive terminal UI")
+        (@arg analyze: --analyze display_order(3)
+            conflicts_with[interactive]
+            "print a report on the pattern's behavior instead of running it")
+        (@arg border: --border display_order(3)
+            "draw a border around each frame when not running interactively")
+        (@arg dump_cells: --("dump-cells") display_order(3)
+            "print the coordinates of each live cell once the game ends")
+        (@arg count_only: --("count-only") display_order(3)
+            conflicts_with[interactive]
+            "print each generation's number and population instead of drawing it")
+        (@arg step: --step display_order(3)
+            +takes_value
+            conflicts_with[interactive]
+            "print only the pattern as it is after this many generations")
+        (@arg separator: --separator display_order(3)
+            +takes_value
+            "line written before each frame, or none if empty [default: blank, or % with --out]")
+        (@arg quiet: -q --quiet display_order(3)
+            conflicts_with_all(&["separator", "border", "interactive"])
+            "write only the frames, with a blank line between each, and no summary at exit")
+        (@arg output: --out display_order(3)
+            +takes_value
+            "write each frame to this file instead of stdout")
         (@arg view: -v --view display_order(3)
             default_value[fixed]
             possible_values(VIEW_CHOICES)
             "viewing mode")
+        (@arg follow_deadzone: --deadzone display_order(3)
+            default_value("0")
+            "distance the pattern may drift before the follow view re-centers")
+        (@arg scroll_margin: --("scroll-margin") display_order(3)
+            +takes_value
+            "stop scrolling this many cells past the pattern's edges [default: unbounded]")
         (@arg width: -w --width display_order(4)
             +takes_value
             "viewport width [default: auto]")
@@ -61,30 +160,214 @@ where
             default_value(*DEFAULT_CHAR_DEAD)
             env[CONWAY_DEAD_CHAR]
             "character used to render dead cells")
+        (@arg trail_char: --("trail-char") display_order(5)
+            default_value(DEFAULT_CHAR_TRAIL)
+            "character used to render dead cells that were once alive, with --trace")
+        (@arg color_alive: --("live-color") display_order(5)
+            +takes_value
+            possible_values(COLOR_CHOICES)
+            "color used to render live cells [default: none]")
+        (@arg show_age: --("show-age") display_order(5)
+            "shade live cells by how many generations they've been alive")
+        (@arg debug_neighbors: --("debug-neighbors") display_order(5)
+            conflicts_with[show_age]
+            "draw each cell as its number of live neighbors")
+        (@arg trace: --trace display_order(5)
+            conflicts_with_all(&["show_age", "debug_neighbors"])
+            "keep drawing cells that were ever alive, tracing the pattern's path")
+        (@arg invert: --invert display_order(5)
+            conflicts_with_all(&["show_age", "debug_neighbors"])
+            "swap how live and dead cells are drawn, including their colors")
+        (@arg halfblock: --halfblock display_order(5)
+            conflicts_with_all(&["show_age", "debug_neighbors", "trace"])
+            "draw two rows of cells per line with half-block characters, so cells look square")
+        (@arg no_menu: --("no-menu") display_order(5)
+            "hide the menu in interactive mode, giving the whole width to the pattern")
+        (@arg grid_width: --("grid-width") display_order(6)
+            +takes_value
+            "width of the universe [default: infinite]")
+        (@arg grid_height: --("grid-height") display_order(6)
+            +takes_value
+            "height of the universe [default: infinite]")
+        (@arg wrap_x: --("wrap-x") display_order(7)
+            requires[grid_width]
+            "wrap cells around the left and right edges")
+        (@arg wrap_y: --("wrap-y") display_order(7)
+            requires[grid_height]
+            "wrap cells around the top and bottom edges")
+        (@arg bounded: --bounded display_order(7)
+            "kill cells that leave the universe instead of letting them go on forever")
+        (@arg neighborhood: --neighborhood display_order(7)
+            default_value("moore")
+            possible_values(NEIGHBORHOOD_CHOICES)
+            "which surrounding cells count as neighbors")
+        (@arg treat_space_as_dead: --("space-as-dead") display_order(8)
+            "treat whitespace in patterns as dead cells")
     ).get_matches_from(args)
 }
 
 #[derive(Debug)]
 pub struct ConfigReader {
     pub settings: Settings,
-    pub pattern: String,
+    pub grid: Grid,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Settings {
     pub delay: Duration,
     pub view: View,
+    pub follow_deadzone: u64,
+    pub scroll_margin: Option<u64>,
+    pub rule: Rule,
+    pub stop_when_stable: bool,
+    pub terminate_when: Vec<TerminationCondition>,
+    pub max_generations: Option<u64>,
+    pub history_len: usize,
+    pub interactive: bool,
+    pub analyze: bool,
+    pub output: Option<PathBuf>,
+    pub border: bool,
+    pub dump_cells: bool,
+    pub count_only: bool,
+    pub step: Option<u64>,
+    pub frame_separator: Option<String>,
+    pub quiet: bool,
 
     pub width: Option<u64>,
     pub height: Option<u64>,
 
     pub char_alive: char,
     pub char_dead: char,
+    pub char_trail: char,
+    pub color_alive: Option<String>,
+    pub show_age: bool,
+    pub debug_neighbors: bool,
+    pub trace: bool,
+    pub invert: bool,
+    pub halfblock: bool,
+    pub no_menu: bool,
+
+    pub grid: GridSettings,
+}
+
+/// GridSettings describes the shape of the universe a `Grid` lives in.
+///
+/// An axis with a size and wrapping enabled connects its opposite edges, so wrapping both axes
+/// produces a torus and wrapping just one produces a cylinder. In a bounded universe, cells can't
+/// exist outside of any axis that has a size.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridSettings {
+    pub width: Option<u64>,
+    pub height: Option<u64>,
+
+    pub wrap_x: bool,
+    pub wrap_y: bool,
+    pub bounded: bool,
+    pub neighborhood: Neighborhood,
+
+    pub treat_space_as_dead: bool,
+}
+
+/// Define a chainable setter for each of the given fields of a builder's settings. Optional
+/// fields accept either a value or None.
+macro_rules! setters {
+    ($($field:ident: $ty:ty),*; optional: $($opt:ident: $opt_ty:ty),*) => {
+        $(
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.0.$field = $field;
+                self
+            }
+        )*
+        $(
+            pub fn $opt<T: Into<Option<$opt_ty>>>(mut self, $opt: T) -> Self {
+                self.0.$opt = $opt.into();
+                self
+            }
+        )*
+    };
+}
+
+/// SettingsBuilder creates Settings one field at a time, starting from the defaults.
+#[derive(Debug, Default, Clone)]
+pub struct SettingsBuilder(Settings);
+
+impl SettingsBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    setters! {
+        delay: Duration,
+        view: View,
+        follow_deadzone: u64,
+        rule: Rule,
+        stop_when_stable: bool,
+        terminate_when: Vec<TerminationCondition>,
+        history_len: usize,
+        interactive: bool,
+        analyze: bool,
+        border: bool,
+        dump_cells: bool,
+        count_only: bool,
+        quiet: bool,
+        char_alive: char,
+        char_dead: char,
+        char_trail: char,
+        show_age: bool,
+        debug_neighbors: bool,
+        trace: bool,
+        invert: bool,
+        halfblock: bool,
+        no_menu: bool,
+        grid: GridSettings;
+        optional:
+        scroll_margin: u64,
+        max_generations: u64,
+        step: u64,
+        output: PathBuf,
+        frame_separator: String,
+        width: u64,
+        height: u64,
+        color_alive: String
+    }
+
+    pub fn build(self) -> Settings {
+        self.0
+    }
+}
+
+/// GridSettingsBuilder creates GridSettings one field at a time, starting from the defaults.
+#[derive(Debug, Default, Clone)]
+pub struct GridSettingsBuilder(GridSettings);
+
+impl GridSettingsBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    setters! {
+        wrap_x: bool,
+        wrap_y: bool,
+        bounded: bool,
+        neighborhood: Neighborhood,
+        treat_space_as_dead: bool;
+        optional:
+        width: u64,
+        height: u64
+    }
+
+    pub fn build(self) -> GridSettings {
+        self.0
+    }
 }
 
 impl ConfigReader {
+    /// Read the command line arguments, with defaults from a `conway.toml` config file if one
+    /// is found.
     pub fn from_env() -> AppResult<ConfigReader> {
-        ConfigReader::from_args(env::args_os())
+        ConfigReader::from_args_with_defaults(env::args_os(), io::stdin(), find_config_file()?)
     }
 
     pub fn from_args<I, T>(args: I) -> AppResult<ConfigReader>
@@ -92,36 +375,316 @@ impl ConfigReader {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        let matches = parse_args(args);
+        ConfigReader::from_args_with_stdin(args, io::stdin())
+    }
 
-        let conf = ConfigReader {
-            settings: Settings {
-                delay: Duration::from_millis(matches.value_of("delay").unwrap().parse()?),
+    /// Like `from_args`, but patterns requested from stdin are read from `stdin` instead.
+    pub fn from_args_with_stdin<I, T, R>(args: I, stdin: R) -> AppResult<ConfigReader>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+        R: Read,
+    {
+        ConfigReader::from_args_with_defaults(args, stdin, FileDefaults::new())
+    }
+
+    /// Like `from_args_with_stdin`, but with `defaults` in place of those from a config file.
+    /// Arguments given on the command line take precedence over them.
+    pub fn from_args_with_defaults<I, T, R>(
+        args: I,
+        stdin: R,
+        defaults: FileDefaults,
+    ) -> AppResult<ConfigReader>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+        R: Read,
+    {
+        let matches = parse_args(args);
+        let value_of = |name| value_or_default(&matches, &defaults, name);
 
-                view: matches.value_of("view").unwrap().parse()?,
+        let delay = match matches.value_of("gps") {
+            Some(_) if matches.occurrences_of("delay") > 0 => {
+                return Err(From::from(
+                    "--delay and --gps can't be used together".to_owned(),
+                ));
+            }
+            Some(gps) => match gps.parse::<u64>()? {
+                0 => return Err(From::from("--gps must be greater than zero".to_owned())),
+                // The delay is measured in whole milliseconds, so anything faster would be 0.
+                gps if gps > 1000 => {
+                    return Err(From::from("--gps must be at most 1000".to_owned()));
+                }
+                gps => Duration::from_millis(1000 / gps),
+            },
+            None => Duration::from_millis(value_of("delay").unwrap().parse()?),
+        };
 
-                width: matches.value_of("width").map(str::parse).transpose()?,
-                height: matches.value_of("height").map(str::parse).transpose()?,
+        let settings = Settings {
+            delay,
 
-                char_alive: matches.value_of("live_char").unwrap().parse()?,
-                char_dead: matches.value_of("dead_char").unwrap().parse()?,
+            view: value_of("view").unwrap().parse()?,
+            follow_deadzone: matches.value_of("follow_deadzone").unwrap().parse()?,
+            scroll_margin: matches
+                .value_of("scroll_margin")
+                .map(str::parse)
+                .transpose()?,
+            rule: value_of("rule").unwrap().parse()?,
+            stop_when_stable: matches.is_present("stop_when_stable"),
+            terminate_when: vec![TerminationCondition::Empty],
+            max_generations: matches
+                .value_of("max_generations")
+                .map(str::parse)
+                .transpose()?,
+            history_len: matches.value_of("history_len").unwrap().parse()?,
+            interactive: matches.is_present("interactive"),
+            analyze: matches.is_present("analyze"),
+            output: matches.value_of("output").map(PathBuf::from),
+            border: matches.is_present("border"),
+            dump_cells: matches.is_present("dump_cells"),
+            count_only: matches.is_present("count_only"),
+            step: matches.value_of("step").map(str::parse).transpose()?,
+            frame_separator: match matches.value_of("separator") {
+                Some("") => None,
+                Some(separator) => Some(separator.to_string()),
+                None if matches.is_present("output") => Some(FRAME_RULE.to_string()),
+                None => Some(String::new()),
             },
-            pattern: {
-                let path = if let Some(file) = matches.value_of("file") {
-                    Path::new(file).to_path_buf()
-                } else {
-                    let file = matches.value_of("sample").unwrap();
-                    Path::new(SAMPLE_DIR).join(file)
-                };
-
-                let mut f = File::open(path)?;
-                let mut pattern = String::new();
-                f.read_to_string(&mut pattern)?;
-                pattern
+            quiet: matches.is_present("quiet"),
+
+            width: value_of("width").map(str::parse).transpose()?,
+            height: value_of("height").map(str::parse).transpose()?,
+
+            char_alive: value_of("live_char").unwrap().parse()?,
+            char_dead: value_of("dead_char").unwrap().parse()?,
+            char_trail: matches.value_of("trail_char").unwrap().parse()?,
+            color_alive: matches.value_of("color_alive").map(String::from),
+            show_age: matches.is_present("show_age"),
+            debug_neighbors: matches.is_present("debug_neighbors"),
+            trace: matches.is_present("trace"),
+            invert: matches.is_present("invert"),
+            halfblock: matches.is_present("halfblock"),
+            no_menu: matches.is_present("no_menu"),
+
+            grid: GridSettings {
+                width: matches.value_of("grid_width").map(str::parse).transpose()?,
+                height: matches
+                    .value_of("grid_height")
+                    .map(str::parse)
+                    .transpose()?,
+
+                wrap_x: matches.is_present("wrap_x"),
+                wrap_y: matches.is_present("wrap_y"),
+                bounded: matches.is_present("bounded"),
+                neighborhood: matches.value_of("neighborhood").unwrap().parse()?,
+
+                treat_space_as_dead: matches.is_present("treat_space_as_dead"),
             },
         };
 
-        Ok(conf)
+        // A fixed-size view with no width or height would never show anything.
+        if settings.view != View::Centered {
+            for &(name, size) in &[("width", settings.width), ("height", settings.height)] {
+                if size == Some(0) {
+                    return Err(From::from(format!(
+                        "viewport {} must be greater than zero for the {:?} view",
+                        name, settings.view
+                    )));
+                }
+            }
+        }
+
+        // Live cells drawn with the age ramp must not look dead.
+        if settings.show_age && AGE_CHARS.contains(settings.char_dead) {
+            return Err(From::from(format!(
+                "the dead character '{}' can't be used with --show-age, which draws live cells \
+                 with '{}'",
+                settings.char_dead, AGE_CHARS
+            )));
+        }
+
+        // A wrapped or bounded universe with no width or height has no room for any cells.
+        let axes = [
+            ("width", settings.grid.width, settings.grid.wrap_x),
+            ("height", settings.grid.height, settings.grid.wrap_y),
+        ];
+        for &(name, size, wrap) in &axes {
+            if size == Some(0) && (wrap || settings.grid.bounded) {
+                return Err(From::from(format!(
+                    "grid {} must be greater than zero when it wraps or is bounded",
+                    name
+                )));
+            }
+        }
+
+        let grid = if let Some(size) = matches.value_of("random") {
+            let (width, height) = parse_size(size)?;
+            let density = matches
+                .value_of("density")
+                .map(str::parse)
+                .transpose()
+                .map_err(|e: ParseFloatError| e.to_string())?
+                .unwrap_or(0.5);
+            let seed = match matches.value_of("seed") {
+                Some(seed) => seed.parse()?,
+                None => SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_err(|e| e.to_string())?
+                    .as_secs(),
+            };
+            Grid::random(width, height, density, seed, settings.grid.clone())
+        } else if let Some(pattern) = matches.value_of("pattern") {
+            Grid::parse(&pattern.replace("\\n", "\n"), settings.grid.clone())?
+        } else if let Some(preset) = matches.value_of("preset") {
+            load_preset(preset, &settings.grid)?
+        } else if matches.value_of("file") == Some("-") {
+            // The interactive UI reads key presses from stdin, so it can't hold the pattern too.
+            if settings.interactive {
+                return Err(From::from(
+                    "can't read the pattern from stdin in interactive mode".to_owned(),
+                ));
+            }
+            Grid::from_reader(stdin, settings.grid.clone())?
+        } else {
+            let path = if let Some(file) = matches.value_of("file") {
+                Path::new(file).to_path_buf()
+            } else {
+                let file = matches.value_of("sample").unwrap_or(DEFAULT_SAMPLE);
+                Path::new(SAMPLE_DIR).join(file)
+            };
+
+            Grid::from_reader(File::open(path)?, settings.grid.clone())?
+        };
+
+        // A Game with no live cells is over before it starts, so there would be nothing to show.
+        if grid.is_empty() {
+            return Err(AppError::Msg("pattern has no live cells".to_string()));
+        }
+
+        Ok(ConfigReader { settings, grid })
+    }
+}
+
+/// Defaults for command line arguments read from a config file, keyed by argument name.
+pub type FileDefaults = HashMap<&'static str, String>;
+
+/// FileConfig holds the options that can be set in a config file.
+#[cfg(feature = "toml")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct FileConfig {
+    view: Option<String>,
+    live_char: Option<char>,
+    dead_char: Option<char>,
+    delay: Option<u64>,
+    width: Option<u64>,
+    height: Option<u64>,
+    rule: Option<String>,
+}
+
+/// Parse the contents of a TOML config file.
+#[cfg(feature = "toml")]
+pub fn parse_config_file(s: &str) -> AppResult<FileDefaults> {
+    let config: FileConfig = toml::from_str(s).map_err(|e| e.to_string())?;
+    let values = vec![
+        ("view", config.view),
+        ("live_char", config.live_char.map(|c| c.to_string())),
+        ("dead_char", config.dead_char.map(|c| c.to_string())),
+        ("delay", config.delay.map(|n| n.to_string())),
+        ("width", config.width.map(|n| n.to_string())),
+        ("height", config.height.map(|n| n.to_string())),
+        ("rule", config.rule),
+    ];
+    Ok(values
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect())
+}
+
+/// Read defaults from `conway.toml` in the current directory, or else in `$XDG_CONFIG_HOME`.
+#[cfg(feature = "toml")]
+fn find_config_file() -> AppResult<FileDefaults> {
+    let mut paths = vec![PathBuf::from(CONFIG_FILE)];
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME") {
+        paths.push(Path::new(&dir).join(CONFIG_FILE));
+    }
+    match paths.into_iter().find(|path| path.is_file()) {
+        Some(path) => {
+            let mut contents = String::new();
+            File::open(path)?.read_to_string(&mut contents)?;
+            parse_config_file(&contents)
+        }
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// Config files are only read with the `toml` feature.
+#[cfg(not(feature = "toml"))]
+fn find_config_file() -> AppResult<FileDefaults> {
+    Ok(HashMap::new())
+}
+
+/// Return the value of the named argument if it was given on the command line, or else its value
+/// in `defaults`, or else its default value.
+fn value_or_default<'a>(
+    matches: &'a ArgMatches,
+    defaults: &'a FileDefaults,
+    name: &str,
+) -> Option<&'a str> {
+    match defaults.get(name) {
+        Some(value) if matches.occurrences_of(name) == 0 => Some(value),
+        _ => matches.value_of(name),
+    }
+}
+
+/// Combine the sample patterns of the named preset into one Grid with the given settings.
+fn load_preset(name: &str, opts: &GridSettings) -> AppResult<Grid> {
+    let patterns = match PRESETS.iter().find(|&&(preset, _)| preset == name) {
+        Some(&(_, patterns)) => patterns,
+        None => return Err(From::from(format!("unknown preset: '{}'", name))),
+    };
+    let mut grid = Grid::with_settings(Vec::new(), opts.clone());
+    for &(sample, offset) in patterns {
+        let path = Path::new(SAMPLE_DIR).join(sample);
+        let pattern = Grid::from_reader(File::open(path)?, opts.clone())?;
+        grid.insert_pattern(&pattern, offset);
+    }
+    Ok(grid)
+}
+
+/// Parse a size given in the form WIDTHxHEIGHT.
+fn parse_size(s: &str) -> AppResult<(u64, u64)> {
+    let mut dimensions = s.splitn(2, 'x');
+    match (dimensions.next(), dimensions.next()) {
+        (Some(width), Some(height)) => Ok((width.parse()?, height.parse()?)),
+        _ => Err(From::from(format!("expected WIDTHxHEIGHT, got '{}'", s))),
+    }
+}
+
+/// Return the ANSI escape code that sets the foreground to the named color.
+pub fn color_code(name: &str) -> Option<String> {
+    let code = match name {
+        "black" => color::Fg(color::Black).to_string(),
+        "red" => color::Fg(color::Red).to_string(),
+        "green" => color::Fg(color::Green).to_string(),
+        "yellow" => color::Fg(color::Yellow).to_string(),
+        "blue" => color::Fg(color::Blue).to_string(),
+        "magenta" => color::Fg(color::Magenta).to_string(),
+        "cyan" => color::Fg(color::Cyan).to_string(),
+        "white" => color::Fg(color::White).to_string(),
+        _ => return None,
+    };
+    Some(code)
+}
+
+impl Settings {
+    /// Return the characters drawn for live and dead cells, which are swapped if `invert` is set.
+    pub fn cell_chars(&self) -> (char, char) {
+        if self.invert {
+            (self.char_dead, self.char_alive)
+        } else {
+            (self.char_alive, self.char_dead)
+        }
     }
 }
 
@@ -130,10 +693,269 @@ impl Default for Settings {
         Settings {
             delay: Duration::from_millis(500),
             view: View::Centered,
+            follow_deadzone: 0,
+            scroll_margin: None,
+            rule: Default::default(),
+            stop_when_stable: false,
+            terminate_when: vec![TerminationCondition::Empty],
+            max_generations: None,
+            history_len: DEFAULT_HISTORY_LEN,
+            interactive: false,
+            analyze: false,
+            output: None,
+            border: false,
+            dump_cells: false,
+            count_only: false,
+            step: None,
+            frame_separator: Some(String::new()),
+            quiet: false,
             width: Some(10),
             height: Some(10),
             char_alive: *CHAR_ALIVE,
             char_dead: *CHAR_DEAD,
+            char_trail: CHAR_TRAIL,
+            color_alive: None,
+            show_age: false,
+            debug_neighbors: false,
+            trace: false,
+            invert: false,
+            halfblock: false,
+            no_menu: false,
+            grid: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use Point;
+
+    #[test]
+    fn test_from_args_size() {
+        let config = ConfigReader::from_args(vec!["conway", "--height", "8"]).unwrap();
+        assert_eq!(config.settings.width, None);
+        assert_eq!(config.settings.height, Some(8));
+    }
+
+    #[test]
+    fn test_from_args_stdin() {
+        let stdin = io::Cursor::new("xxx\n");
+        let config = ConfigReader::from_args_with_stdin(vec!["conway", "-F", "-"], stdin).unwrap();
+        assert_eq!(
+            config.grid,
+            Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0)])
+        );
+
+        let stdin = io::Cursor::new("xxx\n");
+        assert!(
+            ConfigReader::from_args_with_stdin(vec!["conway", "-F", "-", "-i"], stdin).is_err(),
+            "it should not read the pattern from stdin in interactive mode"
+        );
+    }
+
+    #[test]
+    fn test_from_args_pattern() {
+        let glider = Grid::new(vec![
+            Point(1, 0),
+            Point(2, 1),
+            Point(0, 2),
+            Point(1, 2),
+            Point(2, 2),
+        ]);
+        let config = ConfigReader::from_args(vec!["conway", "--pattern", ".x.\n..x\nxxx"]).unwrap();
+        assert_eq!(config.grid, glider);
+        let config =
+            ConfigReader::from_args(vec!["conway", "--pattern", ".x.\\n..x\\nxxx"]).unwrap();
+        assert_eq!(config.grid, glider, "it should unescape \\n");
+        assert!(ConfigReader::from_args(vec!["conway", "--pattern", ".#."]).is_err());
+    }
+
+    #[test]
+    fn test_from_args_separator() {
+        let separator = |args: Vec<&str>| {
+            let args = vec!["conway"].into_iter().chain(args);
+            ConfigReader::from_args(args)
+                .unwrap()
+                .settings
+                .frame_separator
+        };
+        assert_eq!(separator(vec![]), Some("".to_string()));
+        assert_eq!(
+            separator(vec![]),
+            Settings::default().frame_separator,
+            "it should match the default Settings"
+        );
+        assert_eq!(separator(vec!["--out", "x"]), Some("%".to_string()));
+        assert_eq!(
+            separator(vec!["--separator", "==="]),
+            Some("===".to_string())
+        );
+        assert_eq!(separator(vec!["--separator", ""]), None);
+    }
+
+    #[test]
+    fn test_from_args_preset() {
+        let config = ConfigReader::from_args(vec!["conway", "--preset", "gun_demo"]).unwrap();
+        assert_eq!(
+            config.grid.population(),
+            36 + 7,
+            "it should combine a gun and an eater"
+        );
+        assert_eq!(
+            config.grid.bounding_box(),
+            Some((Point(1, 1), Point(43, 29)))
+        );
+        assert!(load_preset("nothing", &Default::default()).is_err());
+    }
+
+    #[test]
+    fn test_from_args_gps() {
+        let config = ConfigReader::from_args(vec!["conway", "--gps", "10"]).unwrap();
+        assert_eq!(config.settings.delay, Duration::from_millis(100));
+        let config = ConfigReader::from_args(vec!["conway", "--fps", "4"]).unwrap();
+        assert_eq!(config.settings.delay, Duration::from_millis(250));
+
+        assert!(
+            ConfigReader::from_args(vec!["conway", "--gps", "10", "--delay", "100"]).is_err(),
+            "it should not allow both --gps and --delay"
+        );
+        assert!(ConfigReader::from_args(vec!["conway", "--gps", "0"]).is_err());
+        let config = ConfigReader::from_args(vec!["conway", "--gps", "1000"]).unwrap();
+        assert_eq!(config.settings.delay, Duration::from_millis(1));
+        assert!(
+            ConfigReader::from_args(vec!["conway", "--gps", "1001"]).is_err(),
+            "it should not allow a delay of less than a millisecond"
+        );
+    }
+
+    #[test]
+    fn test_from_args_empty_pattern() {
+        for &pattern in &["", "...\n...\n", "# Nothing to see here\n"] {
+            let stdin = io::Cursor::new(pattern);
+            match ConfigReader::from_args_with_stdin(vec!["conway", "-F", "-"], stdin) {
+                Err(AppError::Msg(msg)) => assert_eq!(msg, "pattern has no live cells"),
+                result => panic!("expected an error loading {:?}, got {:?}", pattern, result),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_from_args_config_file() {
+        let read = |args: Vec<&str>| {
+            let defaults =
+                parse_config_file("view = \"locked\"\nlive-char = \"@\"\ndelay = 50\n").unwrap();
+            let args = vec!["conway"].into_iter().chain(args);
+            ConfigReader::from_args_with_defaults(args, io::empty(), defaults)
+                .unwrap()
+                .settings
+        };
+        let settings = read(vec![]);
+        assert_eq!(settings.view, View::Locked);
+        assert_eq!(settings.char_alive, '@');
+        assert_eq!(settings.delay, Duration::from_millis(50));
+        assert_eq!(settings.char_dead, *CHAR_DEAD);
+
+        let settings = read(vec!["-v", "follow"]);
+        assert_eq!(
+            settings.view,
+            View::Follow,
+            "it should let the command line override the config file"
+        );
+        assert_eq!(settings.char_alive, '@');
+
+        assert!(parse_config_file("colour = \"red\"").is_err());
+    }
+
+    #[test]
+    fn test_from_args_zero_size() {
+        assert!(
+            ConfigReader::from_args(vec!["conway", "-v", "fixed", "--width", "0"]).is_err(),
+            "it should reject a fixed view with no width"
+        );
+        assert!(
+            ConfigReader::from_args(vec!["conway", "-v", "centered", "--width", "0"]).is_ok(),
+            "it should allow a centered view with no width"
+        );
+    }
+
+    #[test]
+    fn test_from_args_show_age_dead_char() {
+        assert!(ConfigReader::from_args(vec!["conway", "--show-age"]).is_ok());
+        assert!(
+            ConfigReader::from_args(vec!["conway", "--show-age", "--dead-char", "*"]).is_err(),
+            "it should reject a dead character that's also used to shade live cells"
+        );
+    }
+
+    #[test]
+    fn test_from_args_zero_grid_size() {
+        let args = |extra: &[&'static str]| {
+            let mut args = vec!["conway", "--pattern", "x"];
+            args.extend_from_slice(extra);
+            ConfigReader::from_args(args)
+        };
+        assert!(
+            args(&["--grid-width", "0", "--wrap-x"]).is_err(),
+            "it should reject wrapping an axis with no width"
+        );
+        assert!(args(&["--grid-height", "0", "--wrap-y"]).is_err());
+        assert!(
+            args(&["--grid-width", "0", "--bounded"]).is_err(),
+            "it should reject a bounded universe with no width"
+        );
+        assert!(args(&["--grid-width", "0"]).is_ok());
+    }
+
+    #[test]
+    fn test_settings_builder() {
+        let grid = GridSettingsBuilder::new()
+            .width(20)
+            .height(10)
+            .wrap_x(true)
+            .neighborhood(Neighborhood::VonNeumann)
+            .build();
+        assert_eq!(
+            grid,
+            GridSettings {
+                width: Some(20),
+                height: Some(10),
+                wrap_x: true,
+                neighborhood: Neighborhood::VonNeumann,
+                ..Default::default()
+            }
+        );
+
+        let settings = SettingsBuilder::new()
+            .char_alive('*')
+            .view(View::Fixed)
+            .max_generations(100)
+            .width(None)
+            .grid(grid.clone())
+            .build();
+        assert_eq!(settings.char_alive, '*');
+        assert_eq!(settings.view, View::Fixed);
+        assert_eq!(settings.max_generations, Some(100));
+        assert_eq!(
+            settings.width, None,
+            "it should allow unsetting optional fields"
+        );
+        assert_eq!(settings.grid, grid);
+        assert_eq!(
+            settings.char_dead,
+            Settings::default().char_dead,
+            "it should keep the defaults for fields that weren't set"
+        );
+    }
+
+    #[test]
+    fn test_color_code() {
+        assert_eq!(color_code("red"), Some("\x1b[38;5;1m".to_string()));
+        assert_eq!(color_code("white"), Some("\x1b[38;5;7m".to_string()));
+        assert_eq!(color_code("mauve"), None, "it should reject unknown colors");
+        for name in COLOR_CHOICES {
+            assert!(color_code(name).is_some(), "it should support {}", name);
         }
     }
 }
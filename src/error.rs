@@ -0,0 +1,106 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+pub type AppResult<T> = Result<T, AppError>;
+
+#[derive(Debug)]
+pub enum AppError {
+    IO(io::Error),
+    ParseInt(std::num::ParseIntError),
+    ParseChar(std::char::ParseCharError),
+    ParsePoint(String),
+    Msg(String),
+    WithCause(Box<AppError>, Box<Error + Send + Sync + 'static>),
+}
+
+impl AppError {
+    pub fn with_cause<E>(self, err: E) -> AppError
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        AppError::WithCause(Box::new(self), Box::new(err))
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (prefix, msg) = match self {
+            AppError::IO(e) => ("IO failed".to_owned(), e.to_string()),
+            AppError::ParseInt(e) => ("expected an integer".to_owned(), e.to_string()),
+            AppError::ParseChar(e) => ("expected a single character".to_owned(), e.to_string()),
+            AppError::ParsePoint(e) => ("failed to parse point".to_owned(), e.to_string()),
+            AppError::Msg(e) => ("invalid input".to_owned(), e.to_string()),
+            AppError::WithCause(e, cause) => (e.to_string(), cause.to_string()),
+        };
+        write!(f, "conway: {}: {}", prefix, msg)
+    }
+}
+
+impl Error for AppError {
+    fn cause(&self) -> Option<&Error> {
+        if let AppError::WithCause(_, ref err) = *self {
+            Some(&**err)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<String> for AppError {
+    fn from(error: String) -> Self {
+        AppError::Msg(error)
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(error: io::Error) -> Self {
+        AppError::IO(error)
+    }
+}
+
+impl From<std::num::ParseIntError> for AppError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        AppError::ParseInt(error)
+    }
+}
+
+impl From<std::char::ParseCharError> for AppError {
+    fn from(error: std::char::ParseCharError) -> Self {
+        AppError::ParseChar(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {Grid, Point};
+
+    #[test]
+    fn test_parse_errors() {
+        match "(1, x)".parse::<Point>() {
+            Err(AppError::ParsePoint(_)) => (),
+            result => panic!("expected a ParsePoint error, got {:?}", result),
+        }
+        match "xo".parse::<Grid>() {
+            Err(AppError::Msg(_)) => (),
+            result => panic!("expected a Msg error, got {:?}", result),
+        }
+        match Grid::from_life106("1 x") {
+            Err(AppError::ParseInt(_)) => (),
+            result => panic!("expected a ParseInt error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            AppError::ParsePoint("missing value for y".to_owned()).to_string(),
+            "conway: failed to parse point: missing value for y"
+        );
+        assert_eq!(
+            AppError::Msg("oops".to_owned()).to_string(),
+            "conway: invalid input: oops"
+        );
+    }
+}
@@ -1,19 +1,62 @@
+use std::cmp;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(feature = "gif")]
+use std::convert::TryFrom;
+use std::fmt;
+#[cfg(feature = "gif")]
+use std::fs::File;
+use std::hash::{Hash, Hasher};
 use std::mem;
+#[cfg(feature = "gif")]
+use std::path::Path;
 use std::str::FromStr;
-use std::thread;
 
 use num_integer::Integer;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use termion::color;
 
-use config::ConfigReader;
 pub use config::Settings;
+use config::{self, ConfigReader};
 use grid::{Grid, Point};
+use point::Bounds;
 use {AppError, AppResult};
 
+/// A TerminationCondition is a reason for a Game to end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TerminationCondition {
+    /// The Grid has no live cells.
+    Empty,
+    /// The Grid is identical to one of the last `history_len` generations.
+    Stable,
+    /// The Game has reached the given generation.
+    MaxGenerations(u64),
+    /// The population has grown past the given number of cells.
+    PopulationCap(usize),
+}
+
+impl fmt::Display for TerminationCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::TerminationCondition::*;
+        match self {
+            Empty => write!(f, "died out"),
+            Stable => write!(f, "stabilized"),
+            MaxGenerations(max) => write!(f, "reached generation {}", max),
+            PopulationCap(cap) => write!(f, "grew past {} cells", cap),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum View {
     Centered,
     Fixed,
     Follow,
+    Locked,
 }
 
 impl FromStr for View {
@@ -24,14 +67,17 @@ impl FromStr for View {
             "centered" => Ok(View::Centered),
             "fixed" => Ok(View::Fixed),
             "follow" => Ok(View::Follow),
+            "locked" => Ok(View::Locked),
             s => Err(From::from(format!("'{}' is not a valid choice", s))),
         }
     }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Viewport {
     origin: Point,
+    center: Point,
     scroll: Point,
     width: u64,
     height: u64,
@@ -47,77 +93,468 @@ impl Viewport {
     }
 }
 
+/// A Summary describes how a Game played out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    /// The population at the start of the Game.
+    pub initial_population: usize,
+    /// The population when the Game ended.
+    pub final_population: usize,
+    /// The number of generations the Game lasted before it died out or stabilized.
+    pub lifespan: u64,
+}
+
+/// RunStats describe a run of the Game when it ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunStats {
+    /// The number of generations that elapsed.
+    pub generations: u64,
+    /// The highest population the Game reached.
+    pub peak_population: usize,
+    /// The population when the Game ended.
+    pub final_population: usize,
+    /// Why the Game ended, or None if it was stopped before it was over.
+    pub reason: Option<TerminationCondition>,
+}
+
+impl fmt::Display for RunStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "generations: {}, peak population: {}, final population: {}, ",
+            self.generations, self.peak_population, self.final_population
+        )?;
+        match self.reason {
+            Some(reason) => write!(f, "{}", reason),
+            None => write!(f, "stopped"),
+        }
+    }
+}
+
+/// The default number of previous generations a Game remembers. Every tick clones the Grid into
+/// the history, so a longer history costs more time and memory on large patterns.
+pub const DEFAULT_HISTORY_LEN: usize = 16;
+
+/// Characters used to draw live cells by age, from newborn to oldest.
+pub const AGE_CHARS: &str = ".:=+*%#";
+/// The glyphs used to draw a pair of vertically stacked cells as one character, indexed by
+/// whether the top cell is alive (1) plus whether the bottom cell is alive (2).
+pub const HALFBLOCK_CHARS: [char; 4] = [' ', '\u{2580}', '\u{2584}', '\u{2588}'];
+
+/// The number of active cells above which `Game::tick` evaluates cells in parallel.
+#[cfg(feature = "rayon")]
+pub const PARALLEL_THRESHOLD: usize = 1024;
+
+/// The number of generations `Game::classify` simulates before giving up.
+pub const CLASSIFY_GENERATIONS: u64 = 256;
+
+/// A Classification describes the long-term behavior of a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Classification {
+    /// The pattern dies out completely.
+    Extinct,
+    /// The pattern never changes.
+    StillLife,
+    /// The pattern returns to its original state after `period` generations.
+    Oscillator { period: u64 },
+    /// The pattern returns to its original shape after `period` generations, moved by (dx, dy).
+    Spaceship { period: u64, dx: i64, dy: i64 },
+    /// The pattern keeps growing without repeating itself.
+    InfiniteGrowth,
+    /// The pattern could not be classified.
+    Unknown,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Classification::*;
+        match self {
+            Extinct => write!(f, "extinct"),
+            StillLife => write!(f, "still life"),
+            Oscillator { period } => write!(f, "oscillator (period {})", period),
+            Spaceship { period, dx, dy } => {
+                write!(
+                    f,
+                    "spaceship (period {}, moving {})",
+                    period,
+                    Point(*dx, *dy)
+                )
+            }
+            InfiniteGrowth => write!(f, "infinite growth"),
+            Unknown => write!(f, "chaotic"),
+        }
+    }
+}
+
 pub struct GameIter<'a>(&'a mut Game);
 
 impl<'a> Iterator for GameIter<'a> {
     type Item = String;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.0.is_over() {
+        if !self.0.has_next() {
             return None;
         }
         self.0.tick();
-        thread::sleep(self.0.opts.delay);
         Some(self.0.draw())
     }
 }
 
+/// GridIter advances a Game as fast as possible, yielding a copy of each new Grid.
+pub struct GridIter<'a>(&'a mut Game);
+
+impl<'a> Iterator for GridIter<'a> {
+    type Item = Grid;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.0.has_next() {
+            return None;
+        }
+        self.0.tick();
+        Some(self.0.grid.clone())
+    }
+}
+
+/// A RuleFn decides whether a cell lives next generation, given whether it's alive now and its
+/// number of live neighbors. It must be thread-safe so that generations can be computed in
+/// parallel.
+pub type RuleFn = Box<dyn Fn(bool, usize) -> bool + Send + Sync>;
+
+// CustomRule wraps a RuleFn so that a Game can still be debug-printed.
+struct CustomRule(RuleFn);
+
+impl fmt::Debug for CustomRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomRule")
+    }
+}
+
 /// Game holds the high-level gameplay logic.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Game {
     grid: Grid,
+    initial: Grid,
+    #[cfg_attr(feature = "serde", serde(skip))]
     swap: Grid,
     opts: Settings,
     viewport: Viewport,
+    generation: u64,
+    activity: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    peak_population: usize,
+    history: VecDeque<Grid>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    seen: HashMap<u64, usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ages: HashMap<Point, u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    trail: HashSet<Point>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    custom_rule: Option<CustomRule>,
 }
 
 impl Game {
     pub fn load() -> AppResult<Game> {
-        let ConfigReader { settings, pattern } = ConfigReader::from_env()?;
-        let grid = pattern.parse()?;
+        let ConfigReader { settings, grid } = ConfigReader::from_env()?;
         Ok(Game::new(grid, settings))
     }
 
     pub fn new(grid: Grid, opts: Settings) -> Game {
-        let mut swap = grid.clone();
-        swap.clear();
+        let swap = grid.empty_like();
 
-        let (origin, Point(x1, y1)) = grid.calculate_bounds();
+        let (origin, Point(x1, y1)) = grid.bounding_box().unwrap_or_default();
         let (width, height) = ((x1 - origin.0 + 1) as u64, (y1 - origin.1 + 1) as u64);
 
         // FIXME: implement Option instead of relying on 0
         // set min dimensions to at least the starting Grid's natural size
         let viewport = Viewport {
             origin,
+            center: grid.centroid(),
             width: opts.width.unwrap_or(width),
             height: opts.height.unwrap_or(height),
             scroll: Point(0, 0),
         };
 
-        Game {
+        let mut game = Game {
+            initial: grid.clone(),
             grid,
             swap,
             opts,
             viewport,
+            generation: 0,
+            activity: 0,
+            peak_population: 0,
+            history: VecDeque::new(),
+            seen: HashMap::new(),
+            ages: HashMap::new(),
+            trail: HashSet::new(),
+            custom_rule: None,
+        };
+        game.update_ages(|_| 1);
+        game.update_trail();
+        game.update_peak_population();
+
+        // A locked view is centered once on the starting Grid, and stays put from then on.
+        if game.opts.view == View::Locked {
+            game.viewport.origin = game.viewport_centered().0;
+        }
+
+        game
+    }
+
+    /// Restart the Game from the Grid it was created with, forgetting its history.
+    pub fn reset(&mut self) {
+        let custom_rule = self.custom_rule.take();
+        *self = Game::new(self.initial.clone(), self.opts.clone());
+        self.custom_rule = custom_rule;
+    }
+
+    /// Use `f` instead of the configured Rule to decide which cells live each generation. It's
+    /// given whether a cell is alive and its number of live neighbors. Like B0 rules, a rule that
+    /// brings dead cells with no live neighbors to life can't be simulated, so it's rejected.
+    pub fn set_rule_fn(&mut self, f: RuleFn) -> AppResult<()> {
+        if f(false, 0) {
+            return Err(From::from(
+                "rules that bring cells with no live neighbors to life are not supported"
+                    .to_owned(),
+            ));
+        }
+        self.custom_rule = Some(CustomRule(f));
+        Ok(())
+    }
+
+    /// Serialize the Game's full state to JSON.
+    #[cfg(feature = "serde")]
+    pub fn save_json(&self) -> AppResult<String> {
+        Ok(serde_json::to_string(self).map_err(|e| e.to_string())?)
+    }
+
+    /// Load a Game previously saved with `save_json`.
+    ///
+    /// Cell ages aren't saved, so every live cell starts out newborn.
+    #[cfg(feature = "serde")]
+    pub fn load_json(s: &str) -> AppResult<Game> {
+        let mut game: Game = serde_json::from_str(s).map_err(|e| e.to_string())?;
+        game.swap = game.grid.empty_like();
+        for grid in &game.history {
+            *game.seen.entry(grid_hash(grid)).or_insert(0) += 1;
+        }
+        game.update_ages(|_| 1);
+        game.update_trail();
+        game.update_peak_population();
+        Ok(game)
+    }
+
+    /// Render `generations` frames of the Game to an animated GIF at `path`, starting with the
+    /// current generation. Each cell is drawn as a `cell_px` square, black if alive and white
+    /// otherwise.
+    #[cfg(feature = "gif")]
+    pub fn export_gif(&mut self, path: &Path, generations: u64, cell_px: u32) -> AppResult<()> {
+        if cell_px == 0 {
+            return Err(From::from("cell size must be greater than zero".to_owned()));
+        }
+        // GIF dimensions are 16 bits, so larger frames can't be encoded.
+        let scale = |cells: usize, name: &str| {
+            let px = cells as u64 * u64::from(cell_px);
+            u16::try_from(px).map_err(|_| {
+                AppError::Msg(format!(
+                    "a GIF {} of {} pixels is larger than the maximum of {}",
+                    name,
+                    px,
+                    u16::MAX
+                ))
+            })
+        };
+        let render = |matrix: Vec<Vec<bool>>| -> AppResult<gif::Frame> {
+            let height = scale(matrix.len(), "height")?;
+            let width = scale(matrix.first().map_or(0, Vec::len), "width")?;
+            let mut pixels = Vec::with_capacity(width as usize * height as usize);
+            for row in &matrix {
+                for _ in 0..cell_px {
+                    for &alive in row {
+                        for _ in 0..cell_px {
+                            pixels.push(alive as u8);
+                        }
+                    }
+                }
+            }
+            Ok(gif::Frame {
+                width,
+                height,
+                buffer: pixels.into(),
+                ..Default::default()
+            })
+        };
+        let delay = (self.opts.delay.as_millis() / 10) as u16;
+
+        let first = render(self.render_grid())?;
+        let file = File::create(path)?;
+        let palette = [0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00];
+        let mut encoder = gif::Encoder::new(file, first.width, first.height, &palette)
+            .map_err(|e| e.to_string())?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+
+        let mut frame = first;
+        for generation in 0..generations {
+            if generation > 0 {
+                self.tick();
+                frame = render(self.render_grid())?;
+            }
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(|e| e.to_string())?;
         }
+        Ok(())
+    }
+
+    /// Like `export_gif`, but if the pattern returns to its starting state in place within
+    /// `generations` generations, only that many frames are rendered so that the GIF loops
+    /// seamlessly. Other patterns fall back to `generations` frames.
+    #[cfg(feature = "gif")]
+    pub fn export_gif_looping(
+        &mut self,
+        path: &Path,
+        generations: u64,
+        cell_px: u32,
+    ) -> AppResult<()> {
+        let mut probe = Game::new(self.grid.clone(), self.opts.clone());
+        let frames = match probe.detect_period(generations) {
+            Some(period) if probe.grid.displacement(&self.grid) == Some(Point(0, 0)) => period,
+            _ => generations,
+        };
+        self.export_gif(path, frames, cell_px)
+    }
+
+    /// Search random soups for interesting patterns. Each seed from `0` to `trials` fills a
+    /// `width` by `height` region at the given density, which is run for up to `max_gens`
+    /// generations. Return the seeds of the soups that grew or were still changing at the end,
+    /// along with a Summary of each.
+    pub fn soup_search(
+        width: u64,
+        height: u64,
+        density: f64,
+        trials: u64,
+        max_gens: u64,
+    ) -> Vec<(u64, Summary)> {
+        let opts = Settings {
+            stop_when_stable: true,
+            ..Default::default()
+        };
+        (0..trials)
+            .filter_map(|seed| {
+                let grid = Grid::random(width, height, density, seed, Default::default());
+                let mut game = Game::new(grid, opts.clone());
+                let initial_population = game.population();
+                while !game.is_over() && game.generation < max_gens {
+                    game.tick();
+                }
+                let summary = Summary {
+                    initial_population,
+                    final_population: game.population(),
+                    lifespan: game.generation,
+                };
+                if summary.final_population > initial_population || !game.is_over() {
+                    Some((seed, summary))
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
 
     pub fn iter(&mut self) -> GameIter {
         GameIter(self)
     }
 
+    /// Return an iterator over each new generation's Grid, without any delay or drawing.
+    pub fn grids(&mut self) -> GridIter<'_> {
+        GridIter(self)
+    }
+
+    // Return whether the Game should go on to another generation.
+    fn has_next(&self) -> bool {
+        match self.opts.max_generations {
+            Some(max_generations) if self.generation >= max_generations => false,
+            _ => !self.is_over(),
+        }
+    }
+
     pub fn draw(&self) -> String {
-        self.draw_viewport(self.viewport())
+        if self.opts.show_age {
+            self.draw_age()
+        } else if self.opts.debug_neighbors {
+            self.draw_neighbors()
+        } else if self.opts.trace {
+            self.draw_trail()
+        } else if self.opts.halfblock {
+            self.draw_halfblock()
+        } else {
+            self.draw_viewport(self.viewport())
+        }
     }
 
-    fn draw_viewport(&self, (Point(x0, y0), Point(x1, y1)): (Point, Point)) -> String {
+    /// Draw the current viewport, showing dead cells that were once alive with `char_trail`.
+    pub fn draw_trail(&self) -> String {
+        let Bounds(Point(x0, y0), Point(x1, y1)) = self.viewport();
+        let (char_alive, char_dead) = self.opts.cell_chars();
         let mut output = String::new();
         for y in y0..=y1 {
             for x in x0..=x1 {
-                output.push(if self.grid.is_alive(&Point(x, y)) {
-                    self.opts.char_alive
+                let cell = Point(x, y);
+                output.push(if self.grid.is_alive(&cell) {
+                    char_alive
+                } else if self.trail.contains(&cell) {
+                    self.opts.char_trail
                 } else {
-                    self.opts.char_dead
+                    char_dead
+                });
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Draw the current viewport with each pair of rows packed into one line of half-block
+    /// characters, so that cells look roughly square in a terminal.
+    pub fn draw_halfblock(&self) -> String {
+        let rows = self.render_grid();
+        let mut output = String::new();
+        for pair in rows.chunks(2) {
+            for (x, &top) in pair[0].iter().enumerate() {
+                let bottom = pair.get(1).is_some_and(|row| row[x]);
+                let (top, bottom) = (top != self.opts.invert, bottom != self.opts.invert);
+                output.push(HALFBLOCK_CHARS[top as usize + 2 * bottom as usize]);
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Draw the current viewport, showing each cell as its number of live neighbors.
+    pub fn draw_neighbors(&self) -> String {
+        let Bounds(Point(x0, y0), Point(x1, y1)) = self.viewport();
+        let mut output = String::new();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                output.push_str(&self.grid.live_neighbors(&Point(x, y)).to_string());
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Draw the current viewport, shading each live cell by how long it has been alive.
+    pub fn draw_age(&self) -> String {
+        let Bounds(Point(x0, y0), Point(x1, y1)) = self.viewport();
+        let shades: Vec<char> = AGE_CHARS.chars().collect();
+        let mut output = String::new();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                output.push(match self.ages.get(&Point(x, y)) {
+                    Some(&age) => shades[cmp::min(age as usize, shades.len()) - 1],
+                    None => self.opts.char_dead,
                 });
             }
             output.push('\n');
@@ -125,29 +562,134 @@ impl Game {
         output
     }
 
+    fn draw_viewport(&self, viewport: Bounds) -> String {
+        let color_name = self.opts.color_alive.as_ref();
+        let alive = match color_name.and_then(|name| config::color_code(name)) {
+            Some(code) => format!(
+                "{}{}{}",
+                code,
+                self.opts.char_alive,
+                color::Fg(color::Reset)
+            ),
+            None => self.opts.char_alive.to_string(),
+        };
+        let mut output = String::new();
+        for row in self.render_viewport(viewport) {
+            for is_alive in row {
+                // Inverted cells swap characters and styling, so dead cells are drawn as alive.
+                if is_alive != self.opts.invert {
+                    output.push_str(&alive);
+                } else {
+                    output.push(self.opts.char_dead);
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Return the cells within the current viewport as rows of booleans, `true` meaning alive.
+    pub fn render_grid(&self) -> Vec<Vec<bool>> {
+        self.render_viewport(self.viewport())
+    }
+
+    fn render_viewport(&self, Bounds(Point(x0, y0), Point(x1, y1)): Bounds) -> Vec<Vec<bool>> {
+        (y0..=y1)
+            .map(|y| {
+                (x0..=x1)
+                    .map(|x| self.grid.is_alive(&Point(x, y)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Scroll the viewport by the given amount. If `scroll_margin` is set, scrolling stops once
+    /// the viewport would be more than that many cells away from every live cell.
     pub fn scroll(&mut self, dx: i64, dy: i64) {
         self.viewport.scroll = self.viewport.scroll - Point(dx, dy);
+        self.clamp_scroll();
+    }
+
+    // Move the viewport back within `scroll_margin` cells of the pattern's bounding box.
+    fn clamp_scroll(&mut self) {
+        let margin = match self.opts.scroll_margin {
+            Some(margin) if self.opts.view != View::Centered => margin as i64,
+            _ => return,
+        };
+        let (Point(bx0, by0), Point(bx1, by1)) = match self.grid.bounding_box() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let Bounds(Point(vx0, vy0), Point(vx1, vy1)) = self.viewport();
+        let clamp = |v0: i64, v1: i64, b0: i64, b1: i64| {
+            if v1 < b0 - margin {
+                b0 - margin - v1
+            } else if v0 > b1 + margin {
+                b1 + margin - v0
+            } else {
+                0
+            }
+        };
+        self.viewport.scroll =
+            self.viewport.scroll + Point(clamp(vx0, vx1, bx0, bx1), clamp(vy0, vy1, by0, by1));
+    }
+
+    /// Change the size of the viewport, keeping it in the same place.
+    pub fn resize_viewport(&mut self, width: u64, height: u64) {
+        self.viewport.width = width;
+        self.viewport.height = height;
     }
 
-    pub fn viewport(&self) -> (Point, Point) {
+    pub fn viewport(&self) -> Bounds {
         match &self.opts.view {
             View::Fixed => self.viewport_fixed(),
             View::Centered => self.viewport_centered(),
-            _ => unimplemented!(),
+            View::Locked => self.viewport_locked(),
+            View::Follow => self.viewport_follow(),
         }
     }
 
-    pub fn viewport_fixed(&self) -> (Point, Point) {
+    pub fn viewport_fixed(&self) -> Bounds {
         let Point(x0, y0) = self.viewport.origin + self.viewport.scroll;
         let p1 = Point(
             x0 + self.viewport.width as i64,
             y0 + self.viewport.height as i64,
         );
-        (Point(x0, y0), p1)
+        Bounds(Point(x0, y0), p1)
     }
 
-    pub fn viewport_centered(&self) -> (Point, Point) {
-        let (Point(x0, y0), Point(x1, y1)) = self.grid.calculate_bounds();
+    pub fn viewport_locked(&self) -> Bounds {
+        let p0 = self.viewport.origin + self.viewport.scroll;
+        let p1 = p0
+            + Point(
+                self.viewport.width as i64 - 1,
+                self.viewport.height as i64 - 1,
+            );
+        Bounds(p0, p1)
+    }
+
+    pub fn viewport_follow(&self) -> Bounds {
+        let (width, height) = (self.viewport.width as i64, self.viewport.height as i64);
+        let p0 = self.viewport.center + self.viewport.scroll - Point(width / 2, height / 2);
+        Bounds(p0, p0 + Point(width - 1, height - 1))
+    }
+
+    // Move the follow view's center to the pattern's centroid along each axis the centroid has
+    // drifted more than `follow_deadzone` cells away on.
+    fn follow(&mut self) {
+        let Point(x, y) = self.grid.centroid();
+        let Point(cx, cy) = self.viewport.center;
+        let deadzone = self.opts.follow_deadzone as i64;
+        if (x - cx).abs() > deadzone {
+            self.viewport.center.0 = x;
+        }
+        if (y - cy).abs() > deadzone {
+            self.viewport.center.1 = y;
+        }
+    }
+
+    pub fn viewport_centered(&self) -> Bounds {
+        let (Point(x0, y0), Point(x1, y1)) = self.grid.bounding_box().unwrap_or_default();
         let (width, height) = (x1 - x0 + 1, y1 - y0 + 1);
 
         let (dx, dy) = (
@@ -156,44 +698,393 @@ impl Game {
         );
 
         let ((dx0, dx1), (dy0, dy1)) = (split_int(dx), split_int(dy));
-        (Point(x0 - dx0, y0 - dy0), Point(x1 + dx1, y1 + dy1))
+        Bounds(Point(x0 - dx0, y0 - dy0), Point(x1 + dx1, y1 + dy1))
+    }
+
+    /// Return the number of generations that have elapsed since the Game began.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Classify the pattern by simulating up to `CLASSIFY_GENERATIONS` generations of the Game.
+    ///
+    /// The Game is left at whichever generation the pattern was classified at.
+    pub fn classify(&mut self) -> Classification {
+        let mut history = vec![self.grid.clone()];
+        let mut peak_area = 0;
+
+        for _ in 0..CLASSIFY_GENERATIONS {
+            if self.grid.is_empty() {
+                return Classification::Extinct;
+            }
+            self.tick();
+
+            // Compare against previous generations, most recent first.
+            for (age, grid) in history.iter().rev().enumerate() {
+                if let Some(Point(dx, dy)) = self.grid.displacement(grid) {
+                    let period = age as u64 + 1;
+                    return match (period, dx, dy) {
+                        (1, 0, 0) => Classification::StillLife,
+                        (period, 0, 0) => Classification::Oscillator { period },
+                        (period, dx, dy) => Classification::Spaceship { period, dx, dy },
+                    };
+                }
+            }
+
+            history.push(self.grid.clone());
+            if self.generation < CLASSIFY_GENERATIONS / 2 {
+                peak_area = peak_area.max(self.area());
+            }
+        }
+
+        // A pattern that ends up larger than it ever was early on is assumed to grow forever.
+        if self.area() > peak_area {
+            Classification::InfiniteGrowth
+        } else {
+            Classification::Unknown
+        }
+    }
+
+    /// Bring the given cell to life if it's dead, or kill it if it's alive.
+    pub fn toggle_cell(&mut self, cell: Point) {
+        if !self.grid.set_dead(&cell) {
+            self.grid.set_alive(cell);
+        }
+    }
+
+    /// Return the Game's current Grid.
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Return the smallest number of generations after which the pattern repeats, allowing for it
+    /// to have moved, or None if it doesn't repeat within `max_period` generations.
+    ///
+    /// The Game is left at whichever generation the pattern first repeated at.
+    pub fn detect_period(&mut self, max_period: u64) -> Option<u64> {
+        let start = self.grid.clone();
+        for period in 1..=max_period {
+            self.tick();
+            if self.grid.displacement(&start).is_some() {
+                return Some(period);
+            }
+        }
+        None
+    }
+
+    /// Return how far the pattern moves each period along with the period itself, or None if it
+    /// doesn't move or doesn't repeat within `max_period` generations.
+    pub fn spaceship_velocity(&mut self, max_period: u64) -> Option<(Point, u64)> {
+        let start = self.grid.bounding_box()?.0;
+        let period = self.detect_period(max_period)?;
+        let offset = self.grid.bounding_box()?.0 - start;
+        if offset == Point(0, 0) {
+            None
+        } else {
+            Some((offset, period))
+        }
+    }
+
+    /// Describe the pattern's starting state and long-term behavior. The Game itself is left
+    /// untouched.
+    pub fn analyze(&self) -> String {
+        let fresh = || Game::new(self.grid.clone(), self.opts.clone());
+        let bounds = match self.grid.bounding_box() {
+            Some((p0, p1)) => format!("{} to {}", p0, p1),
+            None => "none".to_string(),
+        };
+        let period = match fresh().detect_period(CLASSIFY_GENERATIONS) {
+            Some(period) => period.to_string(),
+            None => "none".to_string(),
+        };
+        let velocity = match fresh().spaceship_velocity(CLASSIFY_GENERATIONS) {
+            Some((offset, period)) => format!("{} every {} generations", offset, period),
+            None => "none".to_string(),
+        };
+        format!(
+            "population:     {}\nbounding box:   {}\nperiod:         {}\nvelocity:       {}\n\
+             classification: {}\n",
+            self.grid.population(),
+            bounds,
+            period,
+            velocity,
+            fresh().classify(),
+        )
+    }
+
+    /// Return the number of cells that were born or died in the last generation.
+    pub fn activity(&self) -> usize {
+        self.activity
+    }
+
+    /// Return an estimate of how many bytes the Game's history takes up: the size of a Point for
+    /// each living cell of each generation held in it. Overhead from the sets themselves isn't
+    /// counted.
+    pub fn history_memory_estimate(&self) -> usize {
+        let cells: usize = self.history.iter().map(Grid::population).sum();
+        cells * mem::size_of::<Point>()
+    }
+
+    /// Return the Game's settings.
+    pub fn settings(&self) -> &Settings {
+        &self.opts
+    }
+
+    /// Return the number of living cells in the Game.
+    pub fn population(&self) -> usize {
+        self.grid.population()
+    }
+
+    /// Return the highest number of living cells the Game has had at once.
+    pub fn peak_population(&self) -> usize {
+        self.peak_population
+    }
+
+    // Record the current population if it's the highest one yet.
+    fn update_peak_population(&mut self) {
+        self.peak_population = cmp::max(self.peak_population, self.grid.population());
+    }
+
+    /// Return the first condition that ends the Game, out of `terminate_when`, then
+    /// `stop_when_stable` and `max_generations`, or None if the Game could go on.
+    pub fn termination_reason(&self) -> Option<TerminationCondition> {
+        let mut conditions = self.opts.terminate_when.clone();
+        if self.opts.stop_when_stable {
+            conditions.push(TerminationCondition::Stable);
+        }
+        if let Some(max_generations) = self.opts.max_generations {
+            conditions.push(TerminationCondition::MaxGenerations(max_generations));
+        }
+        conditions
+            .into_iter()
+            .find(|condition| self.meets(*condition))
+    }
+
+    /// Return statistics about the Game so far, such as to summarize it once it's over.
+    pub fn stats(&self) -> RunStats {
+        RunStats {
+            generations: self.generation,
+            peak_population: self.peak_population,
+            final_population: self.population(),
+            reason: self.termination_reason(),
+        }
+    }
+
+    /// Return the area of the bounding box around the living cells in the Grid.
+    fn area(&self) -> i64 {
+        self.grid
+            .bounding_box()
+            .map_or(0, |(Point(x0, y0), Point(x1, y1))| {
+                (x1 - x0 + 1) * (y1 - y0 + 1)
+            })
     }
 
-    /// Return whether the Game is over. This happens with the Grid is empty.
+    /// Return whether the Game is over. This happens when any of the `terminate_when`
+    /// conditions is met, which by default is just when the Grid is empty, or if the
+    /// `stop_when_stable` setting is enabled, when the Game is stable.
     pub fn is_over(&self) -> bool {
-        self.grid.is_empty()
+        self.opts
+            .terminate_when
+            .iter()
+            .any(|condition| self.meets(*condition))
+            || (self.opts.stop_when_stable && self.is_stable())
+    }
+
+    // Return whether the Game currently meets the given TerminationCondition.
+    fn meets(&self, condition: TerminationCondition) -> bool {
+        match condition {
+            TerminationCondition::Empty => self.grid.is_empty(),
+            TerminationCondition::Stable => self.is_stable(),
+            TerminationCondition::MaxGenerations(max) => self.generation >= max,
+            TerminationCondition::PopulationCap(cap) => self.grid.population() > cap,
+        }
+    }
+
+    /// Return whether the Game has stabilized, meaning the Grid is identical to one of the last
+    /// `history_len` generations. This covers both still lifes and short-period oscillators.
+    ///
+    /// The hash of every Grid in the history is remembered, so most generations can be ruled out
+    /// without comparing whole Grids.
+    pub fn is_stable(&self) -> bool {
+        self.seen.contains_key(&grid_hash(&self.grid))
+            && self.history.iter().any(|grid| grid == &self.grid)
     }
 
     /// Execute the next turn in the Game of Life.
     ///
     /// `tick` applies the rules of game to each individual Point, killing some and reviving others.
     pub fn tick(&mut self) {
-        for cell in self.grid.active_cells() {
-            if self.survives(&cell) {
-                self.swap.set_alive(cell);
+        if self.opts.history_len > 0 {
+            while self.history.len() >= self.opts.history_len {
+                if let Some(grid) = self.history.pop_front() {
+                    self.forget(&grid);
+                }
+            }
+            self.history.push_back(self.grid.clone());
+            *self.seen.entry(grid_hash(&self.grid)).or_insert(0) += 1;
+        }
+        self.advance();
+    }
+
+    // Drop the hash of a Grid that has left the history, once no other Grid in it shares it.
+    fn forget(&mut self, grid: &Grid) {
+        let hash = grid_hash(grid);
+        if let Some(count) = self.seen.get_mut(&hash) {
+            *count -= 1;
+            if *count == 0 {
+                self.seen.remove(&hash);
             }
         }
+    }
+
+    /// Execute the next `n` turns in the Game of Life.
+    ///
+    /// Only the last `history_len` generations are cloned into history, since earlier ones would
+    /// be dropped from it anyway.
+    pub fn tick_n(&mut self, n: u64) {
+        let skip = n.saturating_sub(self.opts.history_len as u64);
+        for _ in 0..skip {
+            self.advance();
+        }
+        for _ in skip..n {
+            self.tick();
+        }
+    }
+
+    /// Run the Game for the given number of generations without any delay or drawing, returning
+    /// the living cells of each generation.
+    pub fn simulate(&mut self, generations: u64) -> Vec<HashSet<Point>> {
+        (0..generations)
+            .map(|_| {
+                self.tick();
+                self.grid.live_cells().cloned().collect()
+            })
+            .collect()
+    }
+
+    // Compute the next generation into the swap Grid and swap it in. The old generation is
+    // cleared first, so the swap Grid is always empty between ticks.
+    fn advance(&mut self) {
+        let survivors = self.survivors();
+        self.swap.set_alive_many(survivors);
+        self.activity = self.grid.count_changes(&self.swap);
         self.grid.clear();
         mem::swap(&mut self.grid, &mut self.swap);
+        self.generation += 1;
+        self.follow();
+        self.update_ages(|age| age + 1);
+        self.update_trail();
+        self.update_peak_population();
     }
 
-    /// Survives returns whether the cell at the given Point survives an application of The Rules.
-    pub fn survives(&self, cell: &Point) -> bool {
-        let live_neighbors = self.grid.live_neighbors(cell);
-        if self.grid.is_alive(cell) {
-            match live_neighbors {
-                2 | 3 => true,
-                _ => false,
+    /// Return the Grid as it was at the given generation, if it's the current generation or one
+    /// still held in the Game's history.
+    pub fn grid_at(&self, generation: u64) -> Option<&Grid> {
+        if generation == self.generation {
+            return Some(&self.grid);
+        }
+        let age = self.generation.checked_sub(generation)? as usize;
+        self.history
+            .len()
+            .checked_sub(age)
+            .and_then(|i| self.history.get(i))
+    }
+
+    /// Restore the previous generation from the Game's history. Return false if there is no
+    /// history left to go back to.
+    pub fn step_back(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(grid) => {
+                self.forget(&grid);
+                self.activity = self.grid.count_changes(&grid);
+                self.grid = grid;
+                self.generation -= 1;
+                self.follow();
+                self.update_ages(|age| cmp::max(age, 2) - 1);
+                true
             }
-        } else {
-            match live_neighbors {
-                3 => true,
-                _ => false,
+            None => false,
+        }
+    }
+
+    /// Return how many generations the cell at the given Point has been continuously alive, if
+    /// ages are being tracked.
+    pub fn age(&self, cell: &Point) -> Option<u32> {
+        self.ages.get(cell).cloned()
+    }
+
+    // Recompute the age of every live cell, applying `f` to the ages of cells that were already
+    // alive. Newborn cells are one generation old. Ages are only tracked when `show_age` is set.
+    fn update_ages<F: Fn(u32) -> u32>(&mut self, f: F) {
+        if !self.opts.show_age {
+            return;
+        }
+        let ages = self
+            .grid
+            .live_cells()
+            .map(|cell| (*cell, self.ages.get(cell).map_or(1, |&age| f(age))))
+            .collect();
+        self.ages = ages;
+    }
+
+    // Remember every live cell as part of the trail. The trail is only tracked when `trace` is set.
+    fn update_trail(&mut self) {
+        if self.opts.trace {
+            self.trail.extend(self.grid.live_cells());
+        }
+    }
+
+    /// Return every cell that will be alive in the next generation.
+    fn survivors(&self) -> Vec<Point> {
+        let counts = self.grid.neighbor_counts();
+        #[cfg(feature = "rayon")]
+        {
+            if counts.len() >= PARALLEL_THRESHOLD {
+                return self.survivors_parallel(counts);
             }
         }
+        self.survivors_sequential(counts)
+    }
+
+    fn survivors_sequential(&self, counts: HashMap<Point, usize>) -> Vec<Point> {
+        counts
+            .into_iter()
+            .filter(|&(cell, live_neighbors)| self.lives(&cell, live_neighbors))
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn survivors_parallel(&self, counts: HashMap<Point, usize>) -> Vec<Point> {
+        counts
+            .into_par_iter()
+            .filter(|&(cell, live_neighbors)| self.lives(&cell, live_neighbors))
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Survives returns whether the cell at the given Point survives an application of The Rules.
+    pub fn survives(&self, cell: &Point) -> bool {
+        self.grid.in_bounds(cell) && self.lives(cell, self.grid.live_neighbors(cell))
+    }
+
+    /// Return whether the cell at the given Point, which has the given number of live neighbors,
+    /// survives an application of The Rules.
+    fn lives(&self, cell: &Point, live_neighbors: usize) -> bool {
+        let alive = self.grid.is_alive(cell);
+        match self.custom_rule {
+            Some(CustomRule(ref f)) => f(alive, live_neighbors),
+            None => self.opts.rule.lives(alive, live_neighbors),
+        }
     }
 }
 
+fn grid_hash(grid: &Grid) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
 fn split_int<T: Integer + Copy>(n: T) -> (T, T) {
     let two = T::one() + T::one();
     let (quotient, remainder) = n.div_rem(&two);
@@ -203,6 +1094,9 @@ fn split_int<T: Integer + Copy>(n: T) -> (T, T) {
 #[cfg(test)]
 mod test {
     use super::*;
+    use grid::{GridSettings, Neighborhood};
+    use std::collections::HashSet;
+    use std::time::{Duration, Instant};
 
     // FIXME: implement Option for width/height to achieve this
     // #[test]
@@ -263,8 +1157,1286 @@ mod test {
         );
     }
 
-    mod viewport {
-        use super::*;
+    #[test]
+    fn test_survivors_glider() {
+        let mut game = Game::new(".x.\n..x\nxxx".parse().unwrap(), Default::default());
+        for _ in 0..8 {
+            let expected: HashSet<Point> = game
+                .grid
+                .active_cells()
+                .into_iter()
+                .filter(|cell| game.survives(cell))
+                .collect();
+            assert_eq!(
+                game.survivors().into_iter().collect::<HashSet<_>>(),
+                expected
+            );
+            game.tick();
+        }
+    }
+
+    #[test]
+    fn test_glider_on_cylinder() {
+        let opts = GridSettings {
+            width: Some(5),
+            wrap_x: true,
+            ..Default::default()
+        };
+        // A glider travelling up and to the right, which moves by (1, -1) every 4 generations.
+        let glider = |Point(dx, dy)| {
+            let cells = vec![
+                Point(0, 0),
+                Point(1, 0),
+                Point(2, 0),
+                Point(2, 1),
+                Point(1, 2),
+            ];
+            Grid::with_settings(
+                cells.into_iter().map(|cell| cell + Point(dx, dy)).collect(),
+                opts.clone(),
+            )
+        };
+
+        let mut game = Game::new(glider(Point(3, 0)), Default::default());
+        for _ in 0..4 {
+            game.tick();
+        }
+        assert_eq!(
+            game.grid,
+            glider(Point(4, -1)),
+            "it should wrap around the right edge"
+        );
+        assert!(game.grid.is_alive(&Point(0, -1)));
+
+        for _ in 0..16 {
+            game.tick();
+        }
+        assert_eq!(
+            game.grid,
+            glider(Point(8, -5)),
+            "it should keep travelling off the top"
+        );
+        let (Point(x0, _), Point(x1, y1)) = game.grid.calculate_bounds();
+        assert!(x0 >= 0 && x1 < 5);
+        assert!(y1 < 0);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut game = Game::new(
+            "x.x\n.x.".parse().unwrap(),
+            Settings {
+                max_generations: Some(10),
+                ..Default::default()
+            },
+        );
+        assert_eq!(game.stats().reason, None);
+        game.tick_n(2);
+        assert_eq!(
+            game.stats(),
+            RunStats {
+                generations: 2,
+                peak_population: 3,
+                final_population: 0,
+                reason: Some(TerminationCondition::Empty),
+            }
+        );
+        game.tick_n(8);
+        assert_eq!(
+            game.stats().reason,
+            Some(TerminationCondition::Empty),
+            "it should give the first condition that was met"
+        );
+    }
+
+    #[test]
+    fn test_stats_display() {
+        let stats = RunStats {
+            generations: 12,
+            peak_population: 8,
+            final_population: 0,
+            reason: Some(TerminationCondition::Empty),
+        };
+        assert_eq!(
+            stats.to_string(),
+            "generations: 12, peak population: 8, final population: 0, died out"
+        );
+        assert_eq!(
+            RunStats {
+                reason: None,
+                ..stats
+            }
+            .to_string(),
+            "generations: 12, peak population: 8, final population: 0, stopped",
+            "it should say when the Game was stopped early"
+        );
+    }
+
+    #[test]
+    fn test_von_neumann() {
+        let pattern = || Grid::new(vec![Point(0, 0), Point(1, 1), Point(2, 2)]);
+        let mut moore = Game::new(pattern(), Default::default());
+        moore.tick();
+        assert!(moore.grid.is_alive(&Point(1, 1)));
+
+        let mut von_neumann = Game::new(
+            Grid::with_settings(
+                pattern().live_cells().cloned().collect(),
+                GridSettings {
+                    neighborhood: Neighborhood::VonNeumann,
+                    ..Default::default()
+                },
+            ),
+            Default::default(),
+        );
+        von_neumann.tick();
+        assert!(
+            !von_neumann.grid.is_alive(&Point(1, 1)),
+            "diagonal neighbors should not count"
+        );
+    }
+
+    #[test]
+    fn test_diagonal_sublattices() {
+        let opts = GridSettings {
+            neighborhood: Neighborhood::Diagonal,
+            ..Default::default()
+        };
+        let diagonal = |cells: Vec<Point>| {
+            Game::new(Grid::with_settings(cells, opts.clone()), Default::default())
+        };
+        let pattern = Grid::parse("xx.x\n.xxx\nx.x.\n.xx.", Default::default()).unwrap();
+        let (even, odd): (Vec<Point>, Vec<Point>) = pattern
+            .live_cells()
+            .cloned()
+            .partition(|&Point(x, y)| (x + y) % 2 == 0);
+        assert!(!even.is_empty() && !odd.is_empty());
+
+        let mut whole = diagonal(pattern.live_cells().cloned().collect());
+        let mut even = diagonal(even);
+        let mut odd = diagonal(odd);
+        for _ in 0..10 {
+            whole.tick();
+            even.tick();
+            odd.tick();
+            let mut expected: Vec<Point> = even.grid.live_cells().cloned().collect();
+            expected.extend(odd.grid.live_cells().cloned());
+            assert_eq!(
+                whole.grid,
+                Grid::with_settings(expected, opts.clone()),
+                "each checkerboard sublattice should evolve independently"
+            );
+        }
+    }
+
+    #[test]
+    fn test_glider_in_bounded_grid() {
+        let opts = GridSettings {
+            width: Some(6),
+            height: Some(6),
+            bounded: true,
+            ..Default::default()
+        };
+        let mut game = Game::new(
+            Grid::parse(".x.\n..x\nxxx", opts.clone()).unwrap(),
+            Default::default(),
+        );
+        for _ in 0..40 {
+            game.tick();
+            for cell in game.grid.live_cells() {
+                assert!(game.grid.in_bounds(cell), "no cell should leave the grid");
+            }
+        }
+        assert!(
+            !game.survives(&Point(6, 5)),
+            "an out-of-bounds cell should never be revived"
+        );
+        assert_eq!(
+            game.grid,
+            Grid::with_settings(
+                vec![Point(4, 4), Point(5, 4), Point(4, 5), Point(5, 5)],
+                opts
+            ),
+            "the glider should crash into the corner and become a block"
+        );
+    }
+
+    #[test]
+    fn test_generation() {
+        let mut game = Game::new(
+            Grid::new(vec![Point(1, 0), Point(1, 1), Point(1, 2)]),
+            Default::default(),
+        );
+        assert_eq!(game.generation(), 0, "it should start at generation 0");
+        game.tick();
+        assert_eq!(game.generation(), 1);
+        game.tick();
+        game.tick();
+        assert_eq!(game.generation(), 3);
+    }
+
+    #[test]
+    fn test_population() {
+        let mut game = Game::new("xxx".parse().unwrap(), Default::default());
+        assert_eq!(game.population(), 3);
+        game.tick();
+        assert_eq!(game.population(), 3);
+        let mut game = Game::new("x.x".parse().unwrap(), Default::default());
+        game.tick();
+        assert_eq!(game.population(), 0);
+    }
+
+    #[test]
+    fn test_iter_max_generations() {
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                delay: Duration::from_millis(0),
+                max_generations: Some(5),
+                ..Default::default()
+            },
+        );
+        assert_eq!(game.iter().count(), 5);
+        assert_eq!(game.generation(), 5);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_survivors_parallel() {
+        let mut game = Game::new(
+            Grid::random(64, 64, 0.4, 7, Default::default()),
+            Default::default(),
+        );
+        for _ in 0..16 {
+            let mut parallel = game.survivors_parallel(game.grid.neighbor_counts());
+            let mut sequential = game.survivors_sequential(game.grid.neighbor_counts());
+            parallel.sort_by_key(|&Point(x, y)| (x, y));
+            sequential.sort_by_key(|&Point(x, y)| (x, y));
+            assert_eq!(parallel, sequential);
+            game.tick();
+        }
+    }
+
+    #[test]
+    fn test_tick_n() {
+        for &history_len in &[0, 2, DEFAULT_HISTORY_LEN] {
+            let opts = Settings {
+                history_len,
+                ..Default::default()
+            };
+            let grid: Grid = ".x.\n..x\nxxx".parse().unwrap();
+            let mut expected = Game::new(grid.clone(), opts.clone());
+            for _ in 0..5 {
+                expected.tick();
+            }
+
+            let mut game = Game::new(grid, opts);
+            game.tick_n(5);
+            assert_eq!(game.grid, expected.grid);
+            assert_eq!(game.generation, expected.generation);
+            assert_eq!(
+                game.history, expected.history,
+                "it should keep the same history as ticking one at a time"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json() {
+        let mut game = Game::new(
+            ".x.\n..x\nxxx".parse().unwrap(),
+            Settings {
+                view: View::Locked,
+                grid: GridSettings {
+                    width: Some(8),
+                    height: Some(8),
+                    wrap_x: true,
+                    wrap_y: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        game.tick_n(7);
+
+        let mut loaded = Game::load_json(&game.save_json().unwrap()).unwrap();
+        assert_eq!(loaded.grid, game.grid);
+        assert_eq!(loaded.generation(), 7);
+        assert_eq!(loaded.settings(), game.settings());
+        assert_eq!(loaded.viewport, game.viewport);
+
+        game.tick_n(20);
+        loaded.tick_n(20);
+        assert_eq!(loaded.grid, game.grid, "it should keep running as before");
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn test_export_gif() {
+        use std::env;
+        use std::fs;
+
+        let path = env::temp_dir().join(format!("conway-{}.gif", std::process::id()));
+        let mut game = Game::new(".x.\n..x\nxxx".parse().unwrap(), Default::default());
+        game.export_gif(&path, 6, 3).unwrap();
+        assert_eq!(game.generation(), 5);
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(&path).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (30, 30));
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        fs::remove_file(&path).unwrap();
+        assert_eq!(frames, 6, "it should write one frame per generation");
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn test_export_gif_invalid_size() {
+        use std::env;
+
+        let path = env::temp_dir().join(format!("conway-size-{}.gif", std::process::id()));
+        let mut game = Game::new("xxx".parse().unwrap(), Default::default());
+        assert!(
+            game.export_gif(&path, 1, 0).is_err(),
+            "it should reject a cell size of zero"
+        );
+
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                view: View::Fixed,
+                width: Some(40_000),
+                height: Some(0),
+                ..Default::default()
+            },
+        );
+        match game.export_gif(&path, 1, 2) {
+            Err(AppError::Msg(_)) => (),
+            result => panic!("expected a frame that's too wide to fail, got {:?}", result),
+        }
+        assert!(!path.exists(), "it should fail before creating the file");
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn test_export_gif_looping() {
+        use std::env;
+        use std::fs;
+
+        let count_frames = |pattern: &str| {
+            let path = env::temp_dir().join(format!("conway-loop-{}.gif", std::process::id()));
+            let mut game = Game::new(pattern.parse().unwrap(), Default::default());
+            game.export_gif_looping(&path, 10, 1).unwrap();
+            let mut decoder = gif::DecodeOptions::new()
+                .read_info(File::open(&path).unwrap())
+                .unwrap();
+            let mut frames = 0;
+            while decoder.read_next_frame().unwrap().is_some() {
+                frames += 1;
+            }
+            fs::remove_file(&path).unwrap();
+            frames
+        };
+        assert_eq!(
+            count_frames("xxx"),
+            2,
+            "it should export one period of a blinker"
+        );
+        assert_eq!(
+            count_frames(".x.\n..x\nxxx"),
+            10,
+            "a moving pattern should fall back to the fixed count"
+        );
+    }
+
+    #[test]
+    fn test_draw_neighbors() {
+        let game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                debug_neighbors: true,
+                width: Some(5),
+                height: Some(5),
+                ..Default::default()
+            },
+        );
+        assert_eq!(game.draw(), "00000\n12321\n11211\n12321\n00000\n");
+    }
+
+    #[test]
+    fn test_activity() {
+        let mut block = Game::new("xx\nxx".parse().unwrap(), Default::default());
+        assert_eq!(block.activity(), 0);
+        block.tick();
+        assert_eq!(block.activity(), 0, "a still life should not change");
+
+        let mut blinker = Game::new("xxx".parse().unwrap(), Default::default());
+        for _ in 0..4 {
+            blinker.tick();
+            assert_eq!(
+                blinker.activity(),
+                4,
+                "two cells should die and two be born"
+            );
+        }
+    }
+
+    #[test]
+    fn test_soup_search() {
+        let results = Game::soup_search(8, 8, 0.5, 10, 50);
+        assert!(!results.is_empty());
+        assert_eq!(
+            results,
+            Game::soup_search(8, 8, 0.5, 10, 50),
+            "it should be deterministic"
+        );
+        for (_, summary) in results {
+            assert!(
+                summary.final_population > summary.initial_population || summary.lifespan == 50
+            );
+        }
+    }
+
+    #[test]
+    fn test_iter_does_not_sleep() {
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                delay: Duration::from_secs(60),
+                max_generations: Some(3),
+                ..Default::default()
+            },
+        );
+        let start = Instant::now();
+        assert_eq!(game.iter().count(), 3);
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "it should leave pacing to the caller"
+        );
+    }
+
+    #[test]
+    fn test_grids() {
+        let mut game = Game::new("xxx".parse().unwrap(), Default::default());
+        let grids: Vec<Grid> = game.grids().take(3).collect();
+        assert_eq!(grids.len(), 3);
+        assert_ne!(grids[0], grids[1]);
+        assert_eq!(grids[0], grids[2], "it should alternate between two states");
+
+        let mut game = Game::new(
+            "x".parse().unwrap(),
+            Settings {
+                delay: Duration::from_secs(60),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            game.grids().count(),
+            1,
+            "it should stop without delay once the game is over"
+        );
+    }
+
+    #[test]
+    fn test_scroll_margin() {
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                view: View::Fixed,
+                width: Some(4),
+                height: Some(4),
+                scroll_margin: Some(2),
+                ..Default::default()
+            },
+        );
+        game.scroll(-100, 0);
+        let Bounds(Point(x0, _), _) = game.viewport();
+        assert_eq!(
+            x0, 4,
+            "it should stop 2 cells past the right edge of the pattern"
+        );
+        game.scroll(100, 100);
+        let Bounds(_, Point(x1, y1)) = game.viewport();
+        assert_eq!(
+            (x1, y1),
+            (-2, -2),
+            "it should stop 2 cells before the top-left of the pattern"
+        );
+
+        game.opts.scroll_margin = None;
+        game.scroll(100, 0);
+        assert_eq!(
+            game.viewport().1,
+            Point(-102, -2),
+            "it should be unbounded by default"
+        );
+    }
+
+    #[test]
+    fn test_draw_trail() {
+        let mut game = Game::new(
+            ".x.\n..x\nxxx".parse().unwrap(),
+            Settings {
+                view: View::Fixed,
+                width: Some(3),
+                height: Some(3),
+                trace: true,
+                ..Default::default()
+            },
+        );
+        game.tick_n(4);
+        assert_eq!(
+            game.draw(),
+            "-.--\n..#-\n...#\n-###\n",
+            "it should draw cells the glider left behind as its trail"
+        );
+
+        game.opts.trace = false;
+        assert_eq!(game.draw(), "----\n--#-\n---#\n-###\n");
+    }
+
+    #[test]
+    fn test_draw_invert() {
+        let pattern = || "x.\n.x".parse().unwrap();
+        let opts = Settings {
+            view: View::Fixed,
+            width: Some(1),
+            height: Some(1),
+            char_alive: '#',
+            char_dead: '-',
+            ..Default::default()
+        };
+        assert_eq!(Game::new(pattern(), opts.clone()).draw(), "#-\n-#\n");
+
+        let game = Game::new(
+            pattern(),
+            Settings {
+                invert: true,
+                ..opts.clone()
+            },
+        );
+        assert_eq!(
+            game.draw(),
+            "-#\n#-\n",
+            "it should draw live cells as dead and dead cells as alive"
+        );
+
+        let game = Game::new(
+            pattern(),
+            Settings {
+                invert: true,
+                color_alive: Some("red".to_owned()),
+                ..opts
+            },
+        );
+        let red = format!("{}#{}", color::Fg(color::Red), color::Fg(color::Reset));
+        assert_eq!(
+            game.draw(),
+            format!("-{}\n{}-\n", red, red),
+            "it should style dead cells instead of live ones"
+        );
+    }
+
+    #[test]
+    fn test_draw_halfblock() {
+        let game = Game::new(
+            "x.x.\n.xx.".parse().unwrap(),
+            Settings {
+                view: View::Fixed,
+                width: Some(3),
+                height: Some(1),
+                halfblock: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            game.draw(),
+            "\u{2580}\u{2584}\u{2588} \n",
+            "it should pack each pair of rows into one line"
+        );
+
+        let game = Game::new(
+            "x.\n.x\nxx".parse().unwrap(),
+            Settings {
+                view: View::Fixed,
+                width: Some(1),
+                height: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            game.draw_halfblock(),
+            "\u{2580}\u{2584}\n\u{2580}\u{2580}\n",
+            "an odd last row should be drawn over dead cells"
+        );
+    }
+
+    #[test]
+    fn test_set_rule_fn() {
+        let mut game = Game::new("xx".parse().unwrap(), Default::default());
+        // In Seeds, every live cell dies and dead cells with exactly 2 neighbors are born.
+        game.set_rule_fn(Box::new(|alive, live_neighbors| {
+            !alive && live_neighbors == 2
+        }))
+        .unwrap();
+        game.tick();
+        assert_eq!(
+            game.grid(),
+            &Grid::new(vec![Point(0, -1), Point(1, -1), Point(0, 1), Point(1, 1)])
+        );
+        game.reset();
+        game.tick();
+        assert_eq!(
+            game.grid().population(),
+            4,
+            "it should keep the custom rule after a reset"
+        );
+
+        assert!(
+            game.set_rule_fn(Box::new(
+                |alive, live_neighbors| !alive && live_neighbors == 0
+            ))
+            .is_err(),
+            "it should reject rules that bring isolated dead cells to life"
+        );
+        game.tick();
+        assert_eq!(
+            game.grid().population(),
+            6,
+            "it should keep the previous rule after rejecting one"
+        );
+    }
+
+    #[test]
+    fn test_history_memory_estimate() {
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                history_len: 3,
+                ..Default::default()
+            },
+        );
+        let generation = 3 * mem::size_of::<Point>();
+        assert_eq!(game.history_memory_estimate(), 0);
+        game.tick();
+        assert_eq!(game.history_memory_estimate(), generation);
+        game.tick();
+        assert_eq!(game.history_memory_estimate(), generation * 2);
+        game.tick_n(10);
+        assert_eq!(
+            game.history_memory_estimate(),
+            generation * 3,
+            "it should stop growing at history_len generations"
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let blinker: Grid = "xxx".parse().unwrap();
+        let mut game = Game::new(blinker.clone(), Default::default());
+        game.scroll(3, -2);
+        for _ in 0..3 {
+            game.tick();
+        }
+        game.toggle_cell(Point(5, 5));
+        game.reset();
+        assert_eq!(game.grid(), &blinker);
+        assert_eq!(game.generation(), 0);
+        assert!(game.history.is_empty());
+        assert_eq!(game.viewport.scroll, Point(0, 0));
+    }
+
+    #[test]
+    fn test_grid_hash() {
+        let mut blinker = Game::new("xxx".parse().unwrap(), Default::default());
+        let horizontal = grid_hash(blinker.grid());
+        blinker.tick();
+        let vertical = grid_hash(blinker.grid());
+        assert_ne!(horizontal, vertical, "each state should have its own hash");
+        blinker.tick();
+        assert_eq!(grid_hash(blinker.grid()), horizontal);
+        assert!(blinker.is_stable());
+
+        let mut block = Game::new("xx\nxx".parse().unwrap(), Default::default());
+        let hash = grid_hash(block.grid());
+        block.tick();
+        assert_eq!(
+            grid_hash(block.grid()),
+            hash,
+            "a still life should repeat its hash"
+        );
+        assert!(block.is_stable());
+        assert_eq!(block.seen.len(), 1);
+    }
+
+    #[test]
+    fn test_seen_follows_history() {
+        let mut game = Game::new(
+            ".x.\n..x\nxxx".parse().unwrap(),
+            Settings {
+                history_len: 4,
+                ..Default::default()
+            },
+        );
+        for _ in 0..50 {
+            game.tick();
+            assert!(
+                game.seen.len() <= 4,
+                "it should forget Grids that leave the history"
+            );
+        }
+        while game.step_back() {}
+        assert!(game.seen.is_empty());
+    }
+
+    #[test]
+    fn test_grid_hash_order_independent() {
+        let cells = vec![Point(0, 0), Point(3, -1), Point(-7, 2)];
+        let reversed = cells.iter().rev().cloned().collect();
+        assert_eq!(
+            grid_hash(&Grid::new(cells)),
+            grid_hash(&Grid::new(reversed))
+        );
+    }
+
+    #[test]
+    fn test_tick_clears_swap() {
+        let mut game = Game::new(".x.\n..x\nxxx".parse().unwrap(), Default::default());
+        assert!(game.swap.is_empty());
+        for _ in 0..2 {
+            game.tick();
+            assert!(
+                game.swap.is_empty(),
+                "it should not leave stale cells in the swap Grid"
+            );
+        }
+        assert_eq!(game.grid().population(), 5);
+    }
+
+    #[test]
+    fn test_dump_cells() {
+        let mut game = Game::new(".x.\n..x\nxxx".parse().unwrap(), Default::default());
+        for _ in 0..4 {
+            game.tick();
+        }
+        assert_eq!(
+            game.grid().to_coordinates(),
+            "2 1\n3 2\n1 3\n2 3\n3 3\n",
+            "it should list the final cells from top to bottom, then left to right"
+        );
+    }
+
+    #[test]
+    fn test_simulate() {
+        let mut game = Game::new("xxx".parse().unwrap(), Default::default());
+        let snapshots = game.simulate(6);
+        assert_eq!(snapshots.len(), 6);
+        assert_eq!(game.generation(), 6);
+        assert_eq!(
+            snapshots[0],
+            hashset!(Point(1, -1), Point(1, 0), Point(1, 1))
+        );
+        assert_ne!(snapshots[0], snapshots[1]);
+        for (i, cells) in snapshots.iter().enumerate().skip(2) {
+            assert_eq!(
+                cells,
+                &snapshots[i % 2],
+                "it should alternate between two states"
+            );
+        }
+    }
+
+    #[test]
+    fn test_grid_at() {
+        let mut game = Game::new(
+            ".x.\n..x\nxxx".parse().unwrap(),
+            Settings {
+                history_len: 3,
+                ..Default::default()
+            },
+        );
+        let mut grids = vec![game.grid.clone()];
+        for _ in 0..5 {
+            game.tick();
+            grids.push(game.grid.clone());
+        }
+
+        for generation in 0..2 {
+            assert_eq!(
+                game.grid_at(generation),
+                None,
+                "it should forget generations older than history_len"
+            );
+        }
+        for generation in 2..6 {
+            assert_eq!(game.grid_at(generation), Some(&grids[generation as usize]));
+        }
+        assert_eq!(game.grid_at(6), None, "it should not know the future");
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut game = Game::new(".x.\n..x\nxxx".parse().unwrap(), Default::default());
+        let start = game.grid.clone();
+        game.tick();
+        let first = game.grid.clone();
+        game.tick();
+        game.tick();
+
+        assert!(game.step_back());
+        assert!(game.step_back());
+        assert_eq!(game.grid, first);
+        assert_eq!(game.generation(), 1);
+        assert!(game.step_back());
+        assert_eq!(game.grid, start);
+        assert_eq!(game.generation(), 0);
+        assert!(
+            !game.step_back(),
+            "it should stop at the start of the history"
+        );
+        assert_eq!(game.grid, start);
+    }
+
+    #[test]
+    fn test_step_back_history_len() {
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                history_len: 2,
+                ..Default::default()
+            },
+        );
+        for _ in 0..5 {
+            game.tick();
+        }
+        assert!(game.step_back());
+        assert!(game.step_back());
+        assert!(!game.step_back(), "it should only remember 2 generations");
+        assert_eq!(game.generation(), 3);
+    }
+
+    mod stable {
+        use super::*;
+
+        #[test]
+        fn test_is_stable_block() {
+            let mut game = Game::new("xx\nxx".parse().unwrap(), Default::default());
+            assert!(
+                !game.is_stable(),
+                "it should not be stable before the first tick"
+            );
+            game.tick();
+            assert!(game.is_stable());
+        }
+
+        #[test]
+        fn test_is_stable_blinker() {
+            let mut game = Game::new("xxx".parse().unwrap(), Default::default());
+            game.tick();
+            assert!(!game.is_stable(), "it should not be stable after one tick");
+            game.tick();
+            assert!(game.is_stable());
+        }
+
+        #[test]
+        fn test_is_stable_glider() {
+            let mut game = Game::new(".x.\n..x\nxxx".parse().unwrap(), Default::default());
+            for _ in 0..8 {
+                game.tick();
+                assert!(!game.is_stable(), "a moving pattern should never be stable");
+            }
+        }
+
+        #[test]
+        fn test_is_over_stop_when_stable() {
+            let mut game = Game::new("xxx".parse().unwrap(), Default::default());
+            game.tick();
+            game.tick();
+            assert!(!game.is_over(), "it should keep going by default");
+
+            let mut game = Game::new(
+                "xxx".parse().unwrap(),
+                Settings {
+                    stop_when_stable: true,
+                    ..Default::default()
+                },
+            );
+            game.tick();
+            assert!(!game.is_over());
+            game.tick();
+            assert!(game.is_over());
+        }
+
+        #[test]
+        fn test_is_over_empty() {
+            let mut game = Game::new("x".parse().unwrap(), Default::default());
+            assert!(!game.is_over());
+            game.tick();
+            assert!(
+                game.is_over(),
+                "it should stop when the grid is empty by default"
+            );
+
+            let mut game = Game::new(
+                "x".parse().unwrap(),
+                Settings {
+                    terminate_when: vec![TerminationCondition::PopulationCap(100)],
+                    ..Default::default()
+                },
+            );
+            game.tick();
+            assert!(
+                !game.is_over(),
+                "it should keep going when Empty isn't a condition"
+            );
+        }
+
+        #[test]
+        fn test_is_over_stable() {
+            let mut game = Game::new(
+                "xx\nxx".parse().unwrap(),
+                Settings {
+                    terminate_when: vec![TerminationCondition::Stable],
+                    ..Default::default()
+                },
+            );
+            assert!(!game.is_over());
+            game.tick();
+            assert!(game.is_over(), "it should stop when the grid is stable");
+        }
+
+        #[test]
+        fn test_is_over_max_generations() {
+            let mut game = Game::new(
+                "xxx".parse().unwrap(),
+                Settings {
+                    terminate_when: vec![TerminationCondition::MaxGenerations(3)],
+                    ..Default::default()
+                },
+            );
+            game.tick_n(2);
+            assert!(!game.is_over());
+            game.tick();
+            assert!(game.is_over(), "it should stop at the max generation");
+        }
+
+        #[test]
+        fn test_is_over_population_cap() {
+            let mut game = Game::new(
+                ".xx\nxx.\n.x.".parse().unwrap(),
+                Settings {
+                    terminate_when: vec![
+                        TerminationCondition::Empty,
+                        TerminationCondition::PopulationCap(100),
+                    ],
+                    ..Default::default()
+                },
+            );
+            while !game.is_over() {
+                assert!(
+                    game.generation() < 1000,
+                    "an exploding pattern should be capped"
+                );
+                game.tick();
+            }
+            assert!(game.population() > 100);
+        }
+    }
+
+    mod classify {
+        use super::*;
+
+        fn classify(pattern: &str) -> Classification {
+            Game::new(pattern.parse().unwrap(), Default::default()).classify()
+        }
+
+        #[test]
+        fn test_classify_block() {
+            assert_eq!(classify("xx\nxx"), Classification::StillLife);
+        }
+
+        #[test]
+        fn test_classify_blinker() {
+            assert_eq!(classify("xxx"), Classification::Oscillator { period: 2 });
+        }
+
+        #[test]
+        fn test_classify_glider() {
+            assert_eq!(
+                classify(".x.\n..x\nxxx"),
+                Classification::Spaceship {
+                    period: 4,
+                    dx: 1,
+                    dy: 1
+                }
+            );
+        }
+
+        #[test]
+        fn test_classify_gosper_glider_gun() {
+            assert_eq!(
+                classify(
+                    "........................x...........\n\
+                     ......................x.x...........\n\
+                     ............xx......xx............xx\n\
+                     ...........x...x....xx............xx\n\
+                     xx........x.....x...xx..............\n\
+                     xx........x...x.xx....x.x...........\n\
+                     ..........x.....x.......x...........\n\
+                     ...........x...x....................\n\
+                     ............xx......................"
+                ),
+                Classification::InfiniteGrowth
+            );
+        }
+
+        #[test]
+        fn test_classify_extinct() {
+            assert_eq!(classify("x.x"), Classification::Extinct);
+        }
+    }
+
+    mod age {
+        use super::*;
+
+        fn game(pattern: &str) -> Game {
+            Game::new(
+                pattern.parse().unwrap(),
+                Settings {
+                    show_age: true,
+                    ..Default::default()
+                },
+            )
+        }
+
+        #[test]
+        fn test_age_block() {
+            let mut game = game("xx\nxx");
+            for generation in 1..5 {
+                for cell in game.grid.live_cells() {
+                    assert_eq!(game.age(cell), Some(generation));
+                }
+                game.tick();
+            }
+        }
+
+        #[test]
+        fn test_age_blinker() {
+            let mut game = game("xxx");
+            game.tick();
+            assert_eq!(
+                game.age(&Point(1, 0)),
+                Some(2),
+                "the center should keep aging"
+            );
+            assert_eq!(
+                game.age(&Point(1, -1)),
+                Some(1),
+                "new arms should be newborn"
+            );
+            assert_eq!(
+                game.age(&Point(1, 1)),
+                Some(1),
+                "new arms should be newborn"
+            );
+            assert_eq!(
+                game.age(&Point(0, 0)),
+                None,
+                "dead cells should have no age"
+            );
+            game.tick();
+            assert_eq!(game.age(&Point(1, 0)), Some(3));
+            assert_eq!(game.age(&Point(0, 0)), Some(1), "it should reset the arms");
+        }
+
+        #[test]
+        fn test_draw_age() {
+            let mut game = Game::new(
+                "xxx".parse().unwrap(),
+                Settings {
+                    show_age: true,
+                    width: Some(5),
+                    height: Some(5),
+                    ..Default::default()
+                },
+            );
+            game.tick();
+            assert_eq!(game.draw(), "-----\n--.--\n--:--\n--.--\n-----\n");
+        }
+
+        #[test]
+        fn test_draw_age_never_dead() {
+            let mut game = Game::new(
+                "xx\nxx".parse().unwrap(),
+                Settings {
+                    show_age: true,
+                    view: View::Fixed,
+                    width: Some(1),
+                    height: Some(1),
+                    ..Default::default()
+                },
+            );
+            for _ in 0..AGE_CHARS.len() + 2 {
+                assert!(
+                    !game.draw().contains(game.opts.char_dead),
+                    "no live cell should be drawn as a dead cell"
+                );
+                game.tick();
+            }
+        }
+    }
+
+    mod period {
+        use super::*;
+
+        fn detect_period(pattern: &str) -> Option<u64> {
+            Game::new(pattern.parse().unwrap(), Default::default()).detect_period(16)
+        }
+
+        #[test]
+        fn test_detect_period_block() {
+            assert_eq!(detect_period("xx\nxx"), Some(1));
+        }
+
+        #[test]
+        fn test_detect_period_blinker() {
+            assert_eq!(detect_period("xxx"), Some(2));
+        }
+
+        #[test]
+        fn test_detect_period_glider() {
+            assert_eq!(
+                detect_period(".x.\n..x\nxxx"),
+                Some(4),
+                "it should recognize the glider after it moves"
+            );
+        }
+
+        #[test]
+        fn test_spaceship_velocity() {
+            let velocity = |pattern: &str| {
+                Game::new(pattern.parse().unwrap(), Default::default()).spaceship_velocity(16)
+            };
+            assert_eq!(velocity(".x.\n..x\nxxx"), Some((Point(1, 1), 4)));
+            assert_eq!(velocity("xxx"), None, "oscillators should not move");
+            assert_eq!(velocity("x"), None);
+        }
+
+        #[test]
+        fn test_analyze_blinker() {
+            let ConfigReader { settings, grid } =
+                ConfigReader::from_args(vec!["conway", "-S", "blinker", "--analyze"]).unwrap();
+            assert!(settings.analyze);
+            let report = Game::new(grid, settings).analyze();
+            assert!(report.contains("period:         2\n"), "{}", report);
+            assert!(
+                report.contains("classification: oscillator (period 2)\n"),
+                "{}",
+                report
+            );
+        }
+
+        #[test]
+        fn test_detect_period_none() {
+            assert_eq!(
+                detect_period("x"),
+                None,
+                "a dying pattern should not repeat"
+            );
+        }
+    }
+
+    mod viewport {
+        use super::*;
+
+        #[test]
+        fn test_viewport_locked() {
+            let mut game = Game::new(
+                ".x.\n..x\nxxx".parse().unwrap(),
+                Settings {
+                    view: View::Locked,
+                    width: Some(7),
+                    height: Some(5),
+                    ..Default::default()
+                },
+            );
+            let viewport = game.viewport();
+            assert_eq!(
+                viewport,
+                game.viewport_centered(),
+                "it should be centered at generation 0"
+            );
+            assert_eq!(viewport, Bounds(Point(-2, -1), Point(4, 3)));
+
+            for _ in 0..8 {
+                game.tick();
+                assert_eq!(
+                    game.viewport(),
+                    viewport,
+                    "it should not follow the pattern"
+                );
+            }
+            assert_ne!(game.viewport_centered(), viewport);
+        }
+
+        #[test]
+        fn test_render_grid() {
+            let game = Game::new(
+                ".x.\n..x\nxxx".parse().unwrap(),
+                Settings {
+                    view: View::Centered,
+                    width: Some(5),
+                    height: Some(4),
+                    ..Default::default()
+                },
+            );
+            let matrix = game.render_grid();
+            assert_eq!(matrix.len(), 4);
+            assert!(matrix.iter().all(|row| row.len() == 5));
+
+            let expected: Vec<Vec<bool>> = game
+                .draw()
+                .lines()
+                .map(|line| line.chars().map(|c| c == game.opts.char_alive).collect())
+                .collect();
+            assert_eq!(matrix, expected, "it should match the drawn viewport");
+        }
+
+        #[test]
+        fn test_viewport_follow() {
+            let mut game = Game::new(
+                ".x.\n..x\nxxx".parse().unwrap(),
+                Settings {
+                    view: View::Follow,
+                    follow_deadzone: 2,
+                    width: Some(7),
+                    height: Some(5),
+                    ..Default::default()
+                },
+            );
+            let viewport = game.viewport();
+            assert_eq!(viewport, Bounds(Point(-2, -1), Point(4, 3)));
+
+            for _ in 0..8 {
+                game.tick();
+                assert_eq!(
+                    game.viewport(),
+                    viewport,
+                    "it should not move while the pattern is within the deadzone"
+                );
+            }
+            game.tick_n(4);
+            assert_eq!(
+                game.viewport(),
+                Bounds(Point(1, 2), Point(7, 6)),
+                "it should re-center once the pattern leaves the deadzone"
+            );
+        }
 
         #[test]
         fn test_viewport_centered_1() {
@@ -277,7 +2449,7 @@ mod test {
                         ..Default::default()
                     }
                 ).viewport_centered(),
-                (Point(-3, -2), Point(3, 4)),
+                Bounds(Point(-3, -2), Point(3, 4)),
                 "should pad content to fit width/height"
             );
         }
@@ -298,7 +2470,7 @@ mod test {
                 ).viewport_centered(),
                 // x0[-12] - 11 = -23 // x1[53] + 11 = 64
                 // y0[1] + 10 = 11 // y1[33] - 11 = 22
-                (Point(-23, 11), Point(64, 22))
+                Bounds(Point(-23, 11), Point(64, 22))
             );
         }
 
@@ -318,7 +2490,7 @@ mod test {
                 ).viewport_centered(),
                 // x0[2] - 3 = -1 // x1[5] + 3 = 8
                 // y0[2] + 0 = 2 // y1[4] + 0 = 4
-                (Point(-1, 2), Point(8, 4)),
+                Bounds(Point(-1, 2), Point(8, 4)),
             );
         }
     }
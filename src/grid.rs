@@ -1,24 +1,243 @@
-use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+#[cfg(feature = "image")]
+use std::path::Path;
 use std::str::FromStr;
 
+#[cfg(feature = "image")]
+use image;
+use num_integer::Integer;
+
+pub use config::GridSettings;
+use point::Bounds;
 pub use point::Point;
 use AppError;
 
 pub const READ_CHAR_ALIVE: char = 'x';
 pub const READ_CHAR_DEAD: char = '.';
 
+pub const LIFE106_HEADER: &str = "#Life 1.06";
+pub const CELLS_CHAR_ALIVE: char = 'O';
+pub const CELLS_CHAR_DEAD: char = '.';
+pub const RLE_CHAR_ALIVE: char = 'o';
+pub const RLE_CHAR_DEAD: char = 'b';
+
+/// A PatternFormat is one of the text formats a Grid can be loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternFormat {
+    /// This crate's own format, where each line is a row of `x` (alive) and `.` (dead) cells.
+    Plaintext,
+    /// One `x y` pair of coordinates per living cell, after a `#Life 1.06` header.
+    Life106,
+    /// Rows of `O` (alive) and `.` (dead) cells, with `!` comments.
+    Cells,
+    /// Run-length encoded rows, after an `x = ..., y = ...` header.
+    Rle,
+}
+
+/// Guess the format of a pattern from its first lines. Anything unrecognized is Plaintext.
+pub fn detect_format(s: &str) -> PatternFormat {
+    let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+    let first = match lines.next() {
+        Some(line) => line,
+        None => return PatternFormat::Plaintext,
+    };
+    if first.starts_with(LIFE106_HEADER) {
+        return PatternFormat::Life106;
+    }
+    if first.starts_with('!') {
+        return PatternFormat::Cells;
+    }
+    // RLE headers may follow any number of `#` comment lines.
+    let header = Some(first)
+        .into_iter()
+        .chain(lines)
+        .find(|line| !line.starts_with('#'));
+    match header {
+        Some(line) if line.starts_with('x') && line[1..].trim_start().starts_with('=') => {
+            PatternFormat::Rle
+        }
+        _ => PatternFormat::Plaintext,
+    }
+}
+
+/// A Neighborhood determines which cells count as a cell's neighbors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Neighborhood {
+    /// The 8 cells surrounding a cell, including diagonals.
+    #[default]
+    Moore,
+    /// The 4 cells directly above, below, left and right of a cell.
+    VonNeumann,
+    /// The 4 cells touching the corners of a cell.
+    Diagonal,
+}
+
+impl FromStr for Neighborhood {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "moore" => Ok(Neighborhood::Moore),
+            "von-neumann" => Ok(Neighborhood::VonNeumann),
+            "diagonal" => Ok(Neighborhood::Diagonal),
+            s => Err(From::from(format!("'{}' is not a valid choice", s))),
+        }
+    }
+}
+
+static MOORE_OFFSETS: &[(i64, i64)] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+static VON_NEUMANN_OFFSETS: &[(i64, i64)] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+static DIAGONAL_OFFSETS: &[(i64, i64)] = &[(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// An Axis along which a Grid can be mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// Mirror left to right.
+    Horizontal,
+    /// Mirror top to bottom.
+    Vertical,
+    /// Mirror across the line running from the top left to the bottom right.
+    Diagonal,
+    /// Mirror across the line running from the top right to the bottom left.
+    AntiDiagonal,
+}
+
+/// A Symmetry is a transformation that leaves a Grid unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Rotation clockwise by the given number of quarter turns.
+    Rotation(u8),
+    /// Reflection along the given Axis.
+    Reflection(Axis),
+}
+
+/// A MergeOp decides which cells stay alive when one Grid is merged into another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOp {
+    /// Keep cells that are alive in either Grid.
+    Union,
+    /// Keep cells that are alive in both Grids.
+    Intersection,
+    /// Keep cells that are alive in exactly one of the Grids.
+    SymmetricDifference,
+}
+
+/// Metadata tags recognized in `#` lines of a pattern, and the keys they're stored under.
+static METADATA_TAGS: &[(char, &str)] = &[('N', "name"), ('C', "comment"), ('O', "author")];
+
 /// A Grid represents the physical world in which Conway's Game of Life takes place.
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Grid {
     cells: HashSet<Point>,
+    opts: GridSettings,
+    #[cfg_attr(feature = "serde", serde(default))]
+    metadata: HashMap<String, String>,
 }
 
 impl Grid {
     /// Create a new Grid.
     pub fn new(cells: Vec<Point>) -> Self {
-        Grid {
-            cells: cells.into_iter().collect(),
+        Grid::with_settings(cells, Default::default())
+    }
+
+    /// Create a new Grid with the given settings.
+    pub fn with_settings(cells: Vec<Point>, opts: GridSettings) -> Self {
+        let mut grid = Grid {
+            cells: HashSet::new(),
+            opts,
+            metadata: HashMap::new(),
+        };
+        grid.set_alive_many(cells);
+        grid
+    }
+
+    /// Create an empty Grid with the same settings and metadata as this one.
+    pub fn empty_like(&self) -> Self {
+        let mut grid = Grid::with_settings(Vec::new(), self.opts.clone());
+        grid.metadata = self.metadata.clone();
+        grid
+    }
+
+    /// Create a new Grid by filling a `width` x `height` region starting at the origin with random
+    /// cells, each of which is alive with a probability of `density`.
+    ///
+    /// The same `seed` always produces the same Grid.
+    pub fn random(width: u64, height: u64, density: f64, seed: u64, opts: GridSettings) -> Self {
+        let mut rng = SplitMix64(seed);
+        let mut cells = Vec::new();
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                if rng.next_f64() < density {
+                    cells.push(Point(x, y));
+                }
+            }
+        }
+        Grid::with_settings(cells, opts)
+    }
+
+    /// Parse a Grid with the given settings from a block of structured text.
+    ///
+    /// Lines starting with `#` are comments. Those tagged `#N`, `#C` or `#O` are kept in the
+    /// Grid's metadata as its name, comment or author, and any others as its comment.
+    pub fn parse(s: &str, opts: GridSettings) -> Result<Self, AppError> {
+        let mut cells = Vec::new();
+        let mut metadata = HashMap::new();
+
+        // Leading whitespace is significant if it represents dead cells, so only trim blank lines.
+        let s = if opts.treat_space_as_dead {
+            s.trim_matches(|ch| ch == '\n' || ch == '\r')
+        } else {
+            s.trim()
+        };
+
+        for line in s.lines().filter(|line| line.starts_with('#')) {
+            parse_metadata(&line[1..], &mut metadata);
+        }
+
+        for (y, line) in s.lines().filter(|line| !line.starts_with('#')).enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                // Living Points are added to the Grid.
+                if ch == READ_CHAR_ALIVE {
+                    cells.push(Point(x as i64, y as i64));
+                // Dead Points are ignored, and any other symbol is an error.
+                } else if ch != READ_CHAR_DEAD && !(opts.treat_space_as_dead && ch.is_whitespace())
+                {
+                    return Err(From::from(format!("unknown character: '{}'", ch)));
+                }
+            }
         }
+
+        let mut grid = Grid::with_settings(cells, opts);
+        grid.metadata = metadata;
+        Ok(grid)
+    }
+
+    /// Read a Grid from any source of text, such as a file or standard input. The format of the
+    /// text is detected with `detect_format`.
+    pub fn from_reader<R: Read>(mut reader: R, opts: GridSettings) -> Result<Self, AppError> {
+        let mut pattern = String::new();
+        reader.read_to_string(&mut pattern)?;
+        let grid = match detect_format(&pattern) {
+            PatternFormat::Plaintext => return Grid::parse(&pattern, opts),
+            PatternFormat::Life106 => Grid::from_life106(&pattern)?,
+            PatternFormat::Cells => Grid::from_cells(&pattern)?,
+            PatternFormat::Rle => Grid::from_rle(&pattern)?,
+        };
+        Ok(Grid::with_settings(grid.cells.into_iter().collect(), opts))
     }
 
     /*
@@ -26,6 +245,9 @@ impl Grid {
      */
 
     /// Return the number of living Points that are adjacent to the given Point.
+    ///
+    /// On a small wrapped Grid, several neighbors can wrap to the same Point, or even to the
+    /// given Point itself. Each distinct living Point other than the given one is counted once.
     pub fn live_neighbors(&self, point: &Point) -> usize {
         self.adjacent_cells(point)
             .iter()
@@ -33,6 +255,29 @@ impl Grid {
             .count()
     }
 
+    /// Return the number of living neighbors of every Point in the Grid that should be evaluated
+    /// for survival. This is computed in a single pass over the living cells.
+    pub fn neighbor_counts(&self) -> HashMap<Point, usize> {
+        let mut counts = HashMap::with_capacity(self.cells.len() * 3);
+        for cell in &self.cells {
+            counts.entry(*cell).or_insert(0);
+            for neighbor in self.adjacent_cells(cell) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Return how many of the Points that should be evaluated for survival have each number of
+    /// living neighbors, from 0 to 8.
+    pub fn neighbors_histogram(&self) -> [usize; 9] {
+        let mut histogram = [0; 9];
+        for count in self.neighbor_counts().values() {
+            histogram[*count] += 1;
+        }
+        histogram
+    }
+
     /// Return the set of all Points in the Grid that should be evaluated for survival.
     pub fn active_cells(&self) -> HashSet<Point> {
         self.cells
@@ -45,21 +290,92 @@ impl Grid {
             .collect()
     }
 
-    /// Return all 8 Points that are directly adjacent to the given Point.
+    /// Return the Points that neighbor the given Point: all 8 that are directly adjacent to it,
+    /// only the 4 orthogonal ones in a von Neumann neighborhood, or only the 4 corner ones in a
+    /// Diagonal neighborhood. Neighbors that wrap around to the same Point are only included once,
+    /// and the given Point is never its own neighbor.
     pub fn adjacent_cells(&self, cell: &Point) -> HashSet<Point> {
-        let Point(x, y) = cell;
-        let mut cells = HashSet::with_capacity(8);
-        for dx in -1..=1 {
-            for dy in -1..=1 {
-                if dx == 0 && dy == 0 {
-                    continue;
-                }
-                cells.insert(Point(x + dx, y + dy));
+        let offsets = match self.opts.neighborhood {
+            Neighborhood::Moore => MOORE_OFFSETS,
+            Neighborhood::VonNeumann => VON_NEUMANN_OFFSETS,
+            Neighborhood::Diagonal => DIAGONAL_OFFSETS,
+        };
+        let center = self.wrap(*cell);
+        let mut cells = HashSet::with_capacity(offsets.len());
+        for &(dx, dy) in offsets {
+            let neighbor = self.wrap(*cell + Point(dx, dy));
+            if neighbor != center && self.in_bounds(&neighbor) {
+                cells.insert(neighbor);
             }
         }
         cells
     }
 
+    /// Return whether the given Point exists in the Grid. This is always true unless the Grid is
+    /// bounded, in which case the Point must lie within the Grid's width and height.
+    pub fn in_bounds(&self, cell: &Point) -> bool {
+        if !self.opts.bounded {
+            return true;
+        }
+        let Point(x, y) = self.wrap(*cell);
+        let in_axis = |n: i64, size: Option<u64>| match size {
+            Some(size) => n >= 0 && n < size as i64,
+            None => true,
+        };
+        in_axis(x, self.opts.width) && in_axis(y, self.opts.height)
+    }
+
+    /// Map the given Point onto the Grid, wrapping it around any axes that are configured to wrap.
+    /// An axis with a size of zero can't be wrapped around, so it's left alone.
+    fn wrap(&self, Point(x, y): Point) -> Point {
+        let wrap_axis = |n: i64, size: Option<u64>, wrap: bool| match size {
+            Some(size) if wrap && size > 0 => n.mod_floor(&(size as i64)),
+            _ => n,
+        };
+        Point(
+            wrap_axis(x, self.opts.width, self.opts.wrap_x),
+            wrap_axis(y, self.opts.height, self.opts.wrap_y),
+        )
+    }
+
+    /// Return an iterator over the Points of every living cell in the Grid, in arbitrary order.
+    pub fn live_cells(&self) -> impl Iterator<Item = &Point> {
+        self.cells.iter()
+    }
+
+    /// Return whether this Grid has exactly the same living cells as `other`, whatever their
+    /// settings.
+    pub fn same_cells(&self, other: &Grid) -> bool {
+        self.cells == other.cells
+    }
+
+    /// Return whether every living cell in this Grid is also alive in `other`, at the same
+    /// position.
+    pub fn is_subset_of(&self, other: &Grid) -> bool {
+        self.cells.is_subset(&other.cells)
+    }
+
+    /// Return the metadata read from the Grid's pattern, such as its `name`, `comment` and
+    /// `author`.
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Return the number of living cells in the Grid.
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Return the number of living cells within the rectangle from `top_left` to `bottom_right`,
+    /// inclusive.
+    pub fn count_live_in_region(&self, top_left: Point, bottom_right: Point) -> usize {
+        let (Point(x0, y0), Point(x1, y1)) = (top_left, bottom_right);
+        self.cells
+            .iter()
+            .filter(|&&Point(x, y)| x0 <= x && x <= x1 && y0 <= y && y <= y1)
+            .count()
+    }
+
     /// Return whether the Grid is empty.
     pub fn is_empty(&self) -> bool {
         self.cells.is_empty()
@@ -67,17 +383,92 @@ impl Grid {
 
     /// Return whether the cell at the given Point is alive.
     pub fn is_alive(&self, cell: &Point) -> bool {
-        self.cells.contains(cell)
+        self.cells.contains(&self.wrap(*cell))
     }
 
     /// Bring the cell at the given Point to life.
     pub fn set_alive(&mut self, cell: Point) -> bool {
-        self.cells.insert(cell)
+        let cell = self.wrap(cell);
+        self.in_bounds(&cell) && self.cells.insert(cell)
     }
 
     /// Kill the cell at the given Point.
     pub fn set_dead(&mut self, cell: &Point) -> bool {
-        self.cells.remove(cell)
+        let cell = self.wrap(*cell);
+        self.cells.remove(&cell)
+    }
+
+    /// Bring the cells at each of the given Points to life.
+    pub fn set_alive_many<I: IntoIterator<Item = Point>>(&mut self, cells: I) {
+        self.set_cells(cells);
+    }
+
+    /// Kill the cells at each of the given Points.
+    pub fn set_dead_many<I: IntoIterator<Item = Point>>(&mut self, cells: I) {
+        self.kill_cells(cells);
+    }
+
+    /// Bring the cells at each of the given Points to life. Return how many were dead before.
+    pub fn set_cells<I: IntoIterator<Item = Point>>(&mut self, cells: I) -> usize {
+        let cells = cells.into_iter();
+        self.cells.reserve(cells.size_hint().0);
+        cells.filter(|&cell| self.set_alive(cell)).count()
+    }
+
+    /// Kill the cells at each of the given Points. Return how many were alive before.
+    pub fn kill_cells<I: IntoIterator<Item = Point>>(&mut self, cells: I) -> usize {
+        cells.into_iter().filter(|cell| self.set_dead(cell)).count()
+    }
+
+    /// Copy every living cell of `other` into this Grid, offset by `at`.
+    pub fn insert_pattern(&mut self, other: &Grid, at: Point) {
+        self.set_alive_many(other.cells.iter().map(|&cell| cell + at));
+    }
+
+    /// Combine the living cells of `other` with those of this Grid according to `op`. This Grid's
+    /// settings are kept.
+    pub fn merge(&mut self, other: &Grid, op: MergeOp) {
+        match op {
+            MergeOp::Union => {
+                self.set_cells(other.cells.iter().cloned());
+            }
+            MergeOp::Intersection => self.cells.retain(|cell| other.cells.contains(cell)),
+            MergeOp::SymmetricDifference => {
+                for &cell in &other.cells {
+                    if !self.set_dead(&cell) {
+                        self.set_alive(cell);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Flip every cell within the given region, bringing dead cells to life and killing living
+    /// ones. Return the net change in population. Cells that the region covers more than once by
+    /// wrapping around are only flipped once.
+    pub fn invert_region(&mut self, (Point(x0, y0), Point(x1, y1)): (Point, Point)) -> i64 {
+        let mut cells = HashSet::new();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                cells.insert(self.wrap(Point(x, y)));
+            }
+        }
+        let mut delta = 0;
+        for cell in cells {
+            if self.set_dead(&cell) {
+                delta -= 1;
+            } else if self.set_alive(cell) {
+                delta += 1;
+            }
+        }
+        delta
+    }
+
+    /// Kill every living cell outside of the given Bounds. Return the number of cells killed.
+    pub fn prune(&mut self, bounds: Bounds) -> usize {
+        let before = self.cells.len();
+        self.cells.retain(|cell| bounds.contains(cell));
+        before - self.cells.len()
     }
 
     /// Clear the Grid of all living cells.
@@ -89,10 +480,11 @@ impl Grid {
      * Geometry
      */
 
-    // Return the lowest and highest X and Y coordinates represented in the Grid.
-    pub fn calculate_bounds(&self) -> (Point, Point) {
+    /// Return the lowest and highest X and Y coordinates represented in the Grid, or None if the
+    /// Grid is empty.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
         let mut cells = self.cells.iter();
-        if let Some(&Point(x, y)) = cells.next() {
+        cells.next().map(|&Point(x, y)| {
             let ((mut x0, mut y0), (mut x1, mut y1)) = ((x, y), (x, y));
             for &Point(x, y) in cells {
                 if x < x0 {
@@ -107,38 +499,394 @@ impl Grid {
                 }
             }
             (Point(x0, y0), Point(x1, y1))
+        })
+    }
+
+    // Return the lowest and highest X and Y coordinates represented in the Grid, or the origin if
+    // the Grid is empty.
+    pub fn calculate_bounds(&self) -> (Point, Point) {
+        self.bounding_box().unwrap_or_default()
+    }
+
+    /// Return the average position of the living cells in the Grid, rounded down.
+    pub fn centroid(&self) -> Point {
+        if self.cells.is_empty() {
+            return Default::default();
+        }
+        let n = self.cells.len() as i64;
+        let Point(x, y) = self
+            .cells
+            .iter()
+            .fold(Point(0, 0), |total, &cell| total + cell);
+        Point(Integer::div_floor(&x, &n), Integer::div_floor(&y, &n))
+    }
+
+    /// Return the offset by which `other` must be moved to match this Grid, if they have the same
+    /// shape.
+    pub fn displacement(&self, other: &Grid) -> Option<Point> {
+        if self.cells.len() != other.cells.len() {
+            return None;
+        }
+        let offset = match (self.bounding_box(), other.bounding_box()) {
+            (Some((p0, _)), Some((q0, _))) => p0 - q0,
+            _ => return Some(Point(0, 0)),
+        };
+        if other
+            .cells
+            .iter()
+            .all(|&cell| self.cells.contains(&(cell + offset)))
+        {
+            Some(offset)
         } else {
-            (Default::default(), Default::default())
+            None
         }
     }
-}
 
-/// Parse a Grid from a block of structured text.
-impl FromStr for Grid {
-    type Err = AppError;
+    /// Return the number of cells that are alive in one of the two Grids but not the other.
+    pub fn count_changes(&self, other: &Grid) -> usize {
+        self.cells.symmetric_difference(&other.cells).count()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Return a copy of this Grid with every living cell moved by `offset`. Cells are wrapped or
+    /// dropped according to the Grid's settings, as if they had been set one by one.
+    pub fn translated(&self, offset: Point) -> Grid {
+        Grid::with_settings(
+            self.cells.iter().map(|&cell| cell + offset).collect(),
+            self.opts.clone(),
+        )
+    }
+
+    /// Return a copy of this Grid moved so that the top-left corner of its bounds is at (0, 0).
+    /// Grids with the same shape are equal once normalized, wherever they are.
+    pub fn normalized(&self) -> Grid {
+        let origin = self.bounding_box().map_or(Point(0, 0), |bounds| bounds.0);
+        Grid {
+            cells: self.cells.iter().map(|&cell| cell - origin).collect(),
+            opts: self.opts.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Return the top-left corner of every place the shape of `needle` appears in this Grid, in
+    /// reading order. The cells within the needle's bounds must match it exactly, so a shape that
+    /// is part of a larger cluster of cells isn't counted.
+    pub fn find_pattern(&self, needle: &Grid) -> Vec<Point> {
+        let needle = needle.normalized();
+        let Point(width, height) = match needle.bounding_box() {
+            Some((_, bottom_right)) => bottom_right,
+            None => return Vec::new(),
+        };
+        // Each match is found from the living cell that comes first in reading order, so
+        // only this Grid's living cells need to be tried as anchors.
+        let anchor = *needle
+            .cells
+            .iter()
+            .min_by_key(|&&Point(x, y)| (y, x))
+            .unwrap();
+        let mut found: Vec<Point> = self
+            .cells
+            .iter()
+            .map(|&cell| cell - anchor)
+            .filter(|&origin| {
+                (0..=height).all(|y| {
+                    (0..=width).all(|x| {
+                        let cell = Point(x, y);
+                        needle.cells.contains(&cell) == self.cells.contains(&(cell + origin))
+                    })
+                })
+            })
+            .collect();
+        found.sort_by_key(|&Point(x, y)| (y, x));
+        found
+    }
+
+    /// Compare this Grid to a later one, returning the cells that were born and the cells that
+    /// died in between.
+    pub fn diff(&self, other: &Grid) -> (HashSet<Point>, HashSet<Point>) {
+        let born = other.cells.difference(&self.cells).cloned().collect();
+        let died = self.cells.difference(&other.cells).cloned().collect();
+        (born, died)
+    }
+
+    /// Return a copy of this Grid rotated clockwise by the given number of quarter turns around
+    /// the center of its bounds.
+    pub fn rotated(&self, quarter_turns: u8) -> Grid {
+        self.transformed(|Point(x, y)| match quarter_turns % 4 {
+            0 => Point(x, y),
+            1 => Point(-y, x),
+            2 => Point(-x, -y),
+            _ => Point(y, -x),
+        })
+    }
+
+    /// Return a copy of this Grid mirrored along the given Axis around the center of its bounds.
+    pub fn reflected(&self, axis: Axis) -> Grid {
+        self.transformed(|Point(x, y)| match axis {
+            Axis::Horizontal => Point(-x, y),
+            Axis::Vertical => Point(x, -y),
+            Axis::Diagonal => Point(y, x),
+            Axis::AntiDiagonal => Point(-y, -x),
+        })
+    }
+
+    /// Return every Symmetry of the Grid. Rotation by three quarter turns is left out, since it
+    /// always goes along with rotation by one.
+    pub fn symmetries(&self) -> Vec<Symmetry> {
+        let candidates = [
+            Symmetry::Rotation(1),
+            Symmetry::Rotation(2),
+            Symmetry::Reflection(Axis::Horizontal),
+            Symmetry::Reflection(Axis::Vertical),
+            Symmetry::Reflection(Axis::Diagonal),
+            Symmetry::Reflection(Axis::AntiDiagonal),
+        ];
+        candidates
+            .iter()
+            .cloned()
+            .filter(|&symmetry| {
+                let grid = match symmetry {
+                    Symmetry::Rotation(quarter_turns) => self.rotated(quarter_turns),
+                    Symmetry::Reflection(axis) => self.reflected(axis),
+                };
+                grid.cells == self.cells
+            })
+            .collect()
+    }
+
+    // Apply `f` to every living cell, then move the result back so it shares this Grid's center.
+    fn transformed<F: Fn(Point) -> Point>(&self, f: F) -> Grid {
+        let center = |(Point(x0, y0), Point(x1, y1))| {
+            Point(
+                Integer::div_floor(&(x0 + x1), &2),
+                Integer::div_floor(&(y0 + y1), &2),
+            )
+        };
+        let cells = Grid::new(self.cells.iter().map(|&cell| f(cell)).collect());
+        let offset = match (self.bounding_box(), cells.bounding_box()) {
+            (Some(before), Some(after)) => center(before) - center(after),
+            _ => return self.clone(),
+        };
+        Grid::with_settings(
+            cells.cells.into_iter().map(|cell| cell + offset).collect(),
+            self.opts.clone(),
+        )
+    }
+
+    /*
+     * Formats
+     */
+
+    /// Parse a Grid from text in the Life 1.06 format, which lists the X and Y coordinates of one
+    /// living cell per line.
+    pub fn from_life106(s: &str) -> Result<Self, AppError> {
+        let mut cells = Vec::new();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut coords = line.split_whitespace();
+            match (coords.next(), coords.next(), coords.next()) {
+                (Some(x), Some(y), None) => cells.push(Point(x.parse()?, y.parse()?)),
+                _ => {
+                    return Err(AppError::ParsePoint(format!(
+                        "expected 'x y' coordinates, got '{}'",
+                        line
+                    )))
+                }
+            }
+        }
+        Ok(Grid::new(cells))
+    }
+
+    /// Render the Grid as text in the Life 1.06 format. Cells are sorted from top to bottom, then
+    /// left to right.
+    pub fn to_life106(&self) -> String {
+        format!("{}\n{}", LIFE106_HEADER, self.to_coordinates())
+    }
+
+    /// Render the X and Y coordinates of each living cell, one cell per line. This is the body of
+    /// the Life 1.06 format, sorted the same way.
+    pub fn to_coordinates(&self) -> String {
+        let mut cells: Vec<&Point> = self.cells.iter().collect();
+        cells.sort_by_key(|&&Point(x, y)| (y, x));
+
+        let mut output = String::new();
+        for Point(x, y) in cells {
+            output.push_str(&format!("{} {}\n", x, y));
+        }
+        output
+    }
+
+    /// Parse a Grid from text in the plaintext `.cells` format. Lines starting with `!` are
+    /// comments.
+    pub fn from_cells(s: &str) -> Result<Self, AppError> {
         let mut cells = Vec::new();
+        let rows = s.lines().filter(|line| !line.starts_with('!'));
+        for (y, line) in rows.enumerate() {
+            for (x, ch) in line.trim_end().chars().enumerate() {
+                match ch {
+                    CELLS_CHAR_ALIVE => cells.push(Point(x as i64, y as i64)),
+                    CELLS_CHAR_DEAD => (),
+                    _ => return Err(From::from(format!("unknown character: '{}'", ch))),
+                }
+            }
+        }
+        Ok(Grid::new(cells))
+    }
 
-        for (y, line) in s
-            .trim()
+    /// Parse a Grid from text in the run-length encoded (RLE) format. Each `b` or `o` may be
+    /// preceded by a count of how many times it repeats, `$` ends a row, and `!` ends the pattern.
+    pub fn from_rle(s: &str) -> Result<Self, AppError> {
+        let mut cells = Vec::new();
+        let body = s
             .lines()
+            .map(str::trim)
             .filter(|line| !line.starts_with('#'))
-            .enumerate()
-        {
-            for (x, ch) in line.chars().enumerate() {
-                // Living Points are added to the Grid.
-                if ch == READ_CHAR_ALIVE {
-                    cells.push(Point(x as i64, y as i64));
-                // Dead Points are ignored, and any other symbol is an error.
-                } else if ch != READ_CHAR_DEAD {
-                    return Err(From::from(format!("unknown character: '{}'", ch)));
+            .skip_while(|line| !line.starts_with('x'))
+            .skip(1);
+        let (mut x, mut y) = (0, 0);
+        let mut count = String::new();
+        'outer: for line in body {
+            for ch in line.chars() {
+                if ch.is_ascii_digit() {
+                    count.push(ch);
+                    continue;
+                }
+                let n: i64 = if count.is_empty() { 1 } else { count.parse()? };
+                count.clear();
+                match ch {
+                    RLE_CHAR_ALIVE => {
+                        cells.extend((x..x + n).map(|x| Point(x, y)));
+                        x += n;
+                    }
+                    RLE_CHAR_DEAD => x += n,
+                    '$' => {
+                        x = 0;
+                        y += n;
+                    }
+                    '!' => break 'outer,
+                    _ if ch.is_whitespace() => (),
+                    _ => return Err(From::from(format!("unknown character: '{}'", ch))),
                 }
             }
         }
-
         Ok(Grid::new(cells))
     }
+
+    /// Read a Grid from the image at `path`, with a live cell for each pixel whose luminance is
+    /// below `threshold`, so dark shapes on a light background come to life. The top-left pixel
+    /// becomes the cell at (0, 0).
+    #[cfg(feature = "image")]
+    pub fn from_image(path: &Path, threshold: u8, opts: GridSettings) -> Result<Self, AppError> {
+        let img = image::open(path)
+            .map_err(|e| match e {
+                image::ImageError::IoError(e) => AppError::from(e),
+                e => From::from(format!("failed to read image: {}", e)),
+            })?
+            .to_luma8();
+        let cells = img
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel.0[0] < threshold)
+            .map(|(x, y, _)| Point(i64::from(x), i64::from(y)))
+            .collect();
+        Ok(Grid::with_settings(cells, opts))
+    }
+
+    /// Render the live part of the Grid in the plaintext `.cells` format. Only the bounding box
+    /// around the living cells is included, so the top-left cell of the output is always (0, 0).
+    pub fn to_cells(&self) -> String {
+        let mut output = String::new();
+        let (Point(x0, y0), Point(x1, y1)) = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => return output,
+        };
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                output.push(if self.cells.contains(&Point(x, y)) {
+                    CELLS_CHAR_ALIVE
+                } else {
+                    CELLS_CHAR_DEAD
+                });
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Grids are equal if they have the same settings and living cells. Metadata is ignored.
+impl PartialEq for Grid {
+    fn eq(&self, other: &Grid) -> bool {
+        self.cells == other.cells && self.opts == other.opts
+    }
+}
+
+impl Eq for Grid {}
+
+/// Hash a Grid by its settings and living cells. The cells are combined with XOR, so the result
+/// doesn't depend on the order they're stored in.
+impl Hash for Grid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let cells = self.cells.iter().fold(0, |acc, cell| {
+            let mut hasher = DefaultHasher::new();
+            cell.hash(&mut hasher);
+            acc ^ hasher.finish()
+        });
+        cells.hash(state);
+        self.cells.len().hash(state);
+        self.opts.hash(state);
+    }
+}
+
+/// Store one comment line of a pattern in `metadata`, under the key for its tag if it has one.
+/// Repeated keys are joined by newlines.
+fn parse_metadata(line: &str, metadata: &mut HashMap<String, String>) {
+    let mut chars = line.chars();
+    let tag = chars.next().and_then(|tag| {
+        METADATA_TAGS
+            .iter()
+            .find(|&&(c, _)| c == tag)
+            .map(|&(_, key)| key)
+    });
+    let (key, value) = match tag {
+        Some(key) => (key, chars.as_str().trim()),
+        None => ("comment", line.trim()),
+    };
+    metadata
+        .entry(key.to_string())
+        .and_modify(|existing| {
+            existing.push('\n');
+            existing.push_str(value);
+        })
+        .or_insert_with(|| value.to_string());
+}
+
+/// SplitMix64 is a small, seedable pseudo-random number generator. It's used in place of an external
+/// crate so that a given seed produces the same random Grid on every platform and release.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Return a number in the range [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Parse a Grid from a block of structured text.
+impl FromStr for Grid {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Default::default())
+    }
 }
 
 #[cfg(test)]
@@ -165,38 +913,174 @@ mod test {
             );
             assert!(Grid::from_str("abc\ndef").is_err())
         }
-    }
 
-    mod cells {
-        use super::*;
+        #[test]
+        fn test_parse_metadata() {
+            let grid = Grid::from_str(
+                "#N Glider\n#O John Conway\n#C The smallest spaceship.\n# It moves diagonally.\n\
+                 .x.\n..x\nxxx",
+            )
+            .unwrap();
+            assert_eq!(grid.metadata()["name"], "Glider");
+            assert_eq!(grid.metadata()["author"], "John Conway");
+            assert_eq!(
+                grid.metadata()["comment"],
+                "The smallest spaceship.\nIt moves diagonally.",
+                "it should keep untagged comments"
+            );
+            assert_eq!(
+                grid,
+                Grid::from_str(".x.\n..x\nxxx").unwrap(),
+                "it should parse the cells the same way"
+            );
+        }
 
         #[test]
-        fn test_active_cells() {
-            let grid = Grid::new(vec![Point(0, 0), Point(1, 1)]);
+        fn test_empty_like() {
+            let opts = GridSettings {
+                width: Some(3),
+                wrap_x: true,
+                ..Default::default()
+            };
+            let grid = Grid::with_settings(vec![Point(0, 0), Point(1, 1)], opts.clone());
+            assert_eq!(grid.empty_like(), Grid::with_settings(vec![], opts));
+        }
+
+        #[test]
+        fn test_from_reader() {
+            use std::io::Cursor;
+
+            let reader = Cursor::new(".x.\n..x\nxxx\n");
             assert_eq!(
-                grid.active_cells(),
-                hashset![
-                    Point(0, 0),
-                    Point(-1, -1),
-                    Point(0, -1),
-                    Point(1, -1),
+                Grid::from_reader(reader, Default::default()).unwrap(),
+                Grid::new(vec![
                     Point(1, 0),
-                    Point(1, 1),
-                    Point(0, 1),
-                    Point(-1, 1),
-                    Point(-1, 0),
-                    Point(2, 0),
                     Point(2, 1),
-                    Point(2, 2),
-                    Point(1, 2),
                     Point(0, 2),
-                ]
-            )
+                    Point(1, 2),
+                    Point(2, 2),
+                ])
+            );
         }
 
         #[test]
-        fn test_live_neighbors() {
-            let grid = Grid::new(vec![Point(-1, -1), Point(-1, -2), Point(0, 0), Point(1, 0)]);
+        fn test_parse_treat_space_as_dead() {
+            let pattern = "  x\n\
+                           x .\t\n\
+                           \n\
+                           .x   \n\
+                           \tx";
+            assert!(
+                Grid::from_str(pattern).is_err(),
+                "spaces should be rejected by default"
+            );
+
+            let grid = Grid::parse(
+                pattern,
+                GridSettings {
+                    treat_space_as_dead: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                grid.cells,
+                hashset![Point(2, 0), Point(0, 1), Point(1, 3), Point(1, 4)]
+            );
+        }
+
+        #[test]
+        fn test_random() {
+            let grid = Grid::random(20, 10, 0.5, 42, Default::default());
+            assert_eq!(
+                grid,
+                Grid::random(20, 10, 0.5, 42, Default::default()),
+                "the same seed should produce the same Grid"
+            );
+            assert_ne!(
+                grid,
+                Grid::random(20, 10, 0.5, 43, Default::default()),
+                "different seeds should produce different Grids"
+            );
+
+            let (Point(x0, y0), Point(x1, y1)) = grid.calculate_bounds();
+            assert!(x0 >= 0 && y0 >= 0 && x1 < 20 && y1 < 10);
+            assert!(grid.population() > 50 && grid.population() < 150);
+        }
+
+        #[test]
+        fn test_random_density() {
+            assert!(Grid::random(20, 10, 0.0, 7, Default::default()).is_empty());
+            assert_eq!(
+                Grid::random(20, 10, 1.0, 7, Default::default()).population(),
+                200
+            );
+        }
+    }
+
+    mod cells {
+        use super::*;
+
+        #[test]
+        fn test_active_cells() {
+            let grid = Grid::new(vec![Point(0, 0), Point(1, 1)]);
+            assert_eq!(
+                grid.active_cells(),
+                hashset![
+                    Point(0, 0),
+                    Point(-1, -1),
+                    Point(0, -1),
+                    Point(1, -1),
+                    Point(1, 0),
+                    Point(1, 1),
+                    Point(0, 1),
+                    Point(-1, 1),
+                    Point(-1, 0),
+                    Point(2, 0),
+                    Point(2, 1),
+                    Point(2, 2),
+                    Point(1, 2),
+                    Point(0, 2),
+                ]
+            )
+        }
+
+        #[test]
+        fn test_adjacent_cells_von_neumann() {
+            let grid = Grid::with_settings(
+                vec![],
+                GridSettings {
+                    neighborhood: Neighborhood::VonNeumann,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(
+                grid.adjacent_cells(&Point(2, -1)),
+                hashset!(Point(2, -2), Point(1, -1), Point(3, -1), Point(2, 0)),
+                "it should only include the orthogonal neighbors"
+            );
+            assert_eq!(Grid::default().adjacent_cells(&Point(2, -1)).len(), 8);
+        }
+
+        #[test]
+        fn test_adjacent_cells_diagonal() {
+            let grid = Grid::with_settings(
+                vec![],
+                GridSettings {
+                    neighborhood: Neighborhood::Diagonal,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(
+                grid.adjacent_cells(&Point(2, -1)),
+                hashset!(Point(1, -2), Point(3, -2), Point(1, 0), Point(3, 0)),
+                "it should only include the corner neighbors"
+            );
+        }
+
+        #[test]
+        fn test_live_neighbors() {
+            let grid = Grid::new(vec![Point(-1, -1), Point(-1, -2), Point(0, 0), Point(1, 0)]);
             assert_eq!(
                 grid.live_neighbors(&Point(0, 0)),
                 2,
@@ -209,6 +1093,31 @@ mod test {
             )
         }
 
+        #[test]
+        fn test_neighbors_histogram() {
+            let grid: Grid = "xxx".parse().unwrap();
+            assert_eq!(
+                grid.neighbors_histogram(),
+                [0, 8, 5, 2, 0, 0, 0, 0, 0],
+                "it should count the 15 cells around a blinker by their live neighbors"
+            );
+            assert_eq!(Grid::default().neighbors_histogram(), [0; 9]);
+        }
+
+        #[test]
+        fn test_neighbor_counts() {
+            let grid = Grid::new(vec![Point(0, 0), Point(1, 1), Point(4, 4)]);
+            let counts = grid.neighbor_counts();
+            assert_eq!(
+                counts.keys().cloned().collect::<HashSet<_>>(),
+                grid.active_cells(),
+                "it should cover every active cell"
+            );
+            for (cell, count) in counts {
+                assert_eq!(count, grid.live_neighbors(&cell));
+            }
+        }
+
         #[test]
         fn test_is_empty() {
             let grid: Grid = Default::default();
@@ -217,6 +1126,148 @@ mod test {
             assert!(!grid.is_empty());
         }
 
+        #[test]
+        fn test_live_cells() {
+            let cells = vec![Point(0, 0), Point(-3, 2), Point(7, -7)];
+            let grid = Grid::new(cells.clone());
+            assert_eq!(
+                grid.live_cells().cloned().collect::<HashSet<_>>(),
+                cells.into_iter().collect()
+            );
+            let grid: Grid = Default::default();
+            assert_eq!(grid.live_cells().count(), 0);
+        }
+
+        #[test]
+        fn test_count_live_in_region() {
+            let grid = Grid::new(vec![Point(0, 0), Point(2, 1), Point(-1, 3), Point(4, 4)]);
+            assert_eq!(
+                grid.count_live_in_region(Point(-1, 0), Point(4, 4)),
+                4,
+                "it should count cells on the edges of the region"
+            );
+            assert_eq!(grid.count_live_in_region(Point(0, 0), Point(2, 3)), 2);
+            assert_eq!(grid.count_live_in_region(Point(5, 0), Point(9, 9)), 0);
+            assert_eq!(
+                grid.count_live_in_region(Point(4, 4), Point(0, 0)),
+                0,
+                "an inverted region should be empty"
+            );
+        }
+
+        #[test]
+        fn test_same_cells() {
+            let cells = vec![Point(0, 0), Point(1, 0), Point(2, 0)];
+            let grid = Grid::new(cells.clone());
+            let wrapped = Grid::with_settings(
+                cells,
+                GridSettings {
+                    width: Some(5),
+                    wrap_x: true,
+                    ..Default::default()
+                },
+            );
+            assert!(grid.same_cells(&wrapped));
+            assert_ne!(grid, wrapped, "different settings should still be unequal");
+            assert!(!grid.same_cells(&Grid::new(vec![Point(0, 0)])));
+        }
+
+        #[test]
+        fn test_is_subset_of() {
+            let glider: Grid = ".x.\n..x\nxxx".parse().unwrap();
+            let scene: Grid = ".x...\n..x..\nxxx..\n....x\n...xx".parse().unwrap();
+            assert!(glider.is_subset_of(&scene));
+            assert!(glider.is_subset_of(&glider));
+            assert!(!scene.is_subset_of(&glider));
+            assert!(
+                !glider.translated(Point(1, 0)).is_subset_of(&scene),
+                "it should only match cells at the same position"
+            );
+            assert!(Grid::default().is_subset_of(&glider));
+        }
+
+        #[test]
+        fn test_population() {
+            let mut grid: Grid = Default::default();
+            assert_eq!(grid.population(), 0);
+            grid.set_alive(Point(0, 0));
+            grid.set_alive(Point(-5, 2));
+            assert_eq!(grid.population(), 2);
+            grid.set_alive(Point(0, 0));
+            assert_eq!(
+                grid.population(),
+                2,
+                "reviving a live cell should not count twice"
+            );
+            grid.set_dead(&Point(0, 0));
+            assert_eq!(grid.population(), 1);
+            grid.set_dead(&Point(8, 8));
+            assert_eq!(
+                grid.population(),
+                1,
+                "killing a dead cell should have no effect"
+            );
+        }
+
+        #[test]
+        fn test_prune() {
+            let mut grid: Grid = ".x.\nxxx\n.x.".parse().unwrap();
+            assert_eq!(grid.prune(Bounds(Point(1, 1), Point(1, 1))), 4);
+            assert_eq!(
+                grid.cells,
+                hashset![Point(1, 1)],
+                "it should leave only the cells inside the bounds"
+            );
+            assert_eq!(
+                grid.prune(Bounds(Point(1, 1), Point(5, 5))),
+                0,
+                "it should keep cells on the edges"
+            );
+        }
+
+        #[test]
+        fn test_invert_region() {
+            let mut grid = Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0), Point(1, 1)]);
+            assert_eq!(grid.invert_region((Point(1, -1), Point(3, 0))), 2);
+            assert_eq!(
+                grid.cells,
+                hashset![
+                    Point(0, 0),
+                    Point(1, -1),
+                    Point(2, -1),
+                    Point(3, -1),
+                    Point(3, 0),
+                    Point(1, 1),
+                ],
+                "cells inside the region should flip and cells outside should be untouched"
+            );
+            assert_eq!(grid.invert_region((Point(1, -1), Point(3, 0))), -2);
+            assert_eq!(
+                grid,
+                Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0), Point(1, 1)])
+            );
+        }
+
+        #[test]
+        fn test_invert_region_bounded() {
+            let opts = GridSettings {
+                width: Some(3),
+                height: Some(3),
+                bounded: true,
+                ..Default::default()
+            };
+            let mut grid = Grid::with_settings(vec![Point(1, 0)], opts.clone());
+            assert_eq!(
+                grid.invert_region((Point(0, 0), Point(5, 0))),
+                1,
+                "it should only count cells that are inside the bounds"
+            );
+            assert_eq!(
+                grid,
+                Grid::with_settings(vec![Point(0, 0), Point(2, 0)], opts)
+            );
+        }
+
         #[test]
         fn test_is_alive() {
             let grid = Grid::new(vec![Point(-1, 4), Point(8, 8)]);
@@ -237,9 +1288,607 @@ mod test {
         }
     }
 
+    mod bulk {
+        use super::*;
+
+        fn cells() -> Vec<Point> {
+            (0..1000).map(|i| Point(i % 40, i / 40 - 12)).collect()
+        }
+
+        #[test]
+        fn test_set_alive_many() {
+            let mut expected: Grid = Default::default();
+            for cell in cells() {
+                expected.set_alive(cell);
+            }
+
+            let mut grid: Grid = Default::default();
+            grid.set_alive_many(cells());
+            assert_eq!(grid, expected);
+            assert!(grid.cells.capacity() >= 1000);
+        }
+
+        #[test]
+        fn test_set_dead_many() {
+            let mut grid = Grid::new(cells());
+            grid.set_alive(Point(-1, -1));
+            grid.set_dead_many(cells());
+            assert_eq!(grid, Grid::new(vec![Point(-1, -1)]));
+        }
+
+        #[test]
+        fn test_set_cells() {
+            let mut grid = Grid::new(vec![Point(0, 0)]);
+            assert_eq!(
+                grid.set_cells(vec![Point(0, 0), Point(3, -1), Point(3, -1), Point(-5, 2)]),
+                2,
+                "it should only count cells that were dead"
+            );
+            assert_eq!(grid.population(), 3);
+            assert!(grid.is_alive(&Point(3, -1)));
+            assert!(grid.is_alive(&Point(-5, 2)));
+
+            assert_eq!(grid.kill_cells(vec![Point(0, 0), Point(1, 1)]), 1);
+            assert_eq!(grid.population(), 2);
+            assert!(!grid.is_alive(&Point(0, 0)));
+        }
+
+        #[test]
+        fn test_insert_pattern() {
+            let blinker = Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0)]);
+            let mut grid: Grid = Default::default();
+            grid.insert_pattern(&blinker, Point(0, 0));
+            grid.insert_pattern(&blinker, Point(-1, 5));
+            grid.insert_pattern(&blinker, Point(1, 0));
+            assert_eq!(
+                grid.cells,
+                hashset!(
+                    Point(0, 0),
+                    Point(1, 0),
+                    Point(2, 0),
+                    Point(3, 0),
+                    Point(-1, 5),
+                    Point(0, 5),
+                    Point(1, 5),
+                ),
+                "it should combine overlapping patterns"
+            );
+        }
+
+        #[test]
+        fn test_merge() {
+            let a = Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0)]);
+            let b = Grid::new(vec![Point(1, 0), Point(1, 1), Point(2, 0)]);
+
+            let mut grid = a.clone();
+            grid.merge(&b, MergeOp::Union);
+            assert_eq!(
+                grid.cells,
+                hashset![Point(0, 0), Point(1, 0), Point(2, 0), Point(1, 1)]
+            );
+
+            let mut grid = a.clone();
+            grid.merge(&b, MergeOp::Intersection);
+            assert_eq!(grid.cells, hashset![Point(1, 0), Point(2, 0)]);
+
+            let mut grid = a.clone();
+            grid.merge(&b, MergeOp::SymmetricDifference);
+            assert_eq!(grid.cells, hashset![Point(0, 0), Point(1, 1)]);
+        }
+
+        #[test]
+        fn test_merge_keeps_settings() {
+            let opts = GridSettings {
+                width: Some(2),
+                height: Some(2),
+                bounded: true,
+                ..Default::default()
+            };
+            let mut grid = Grid::with_settings(vec![Point(0, 0)], opts.clone());
+            grid.merge(&Grid::new(vec![Point(1, 1), Point(5, 5)]), MergeOp::Union);
+            assert_eq!(grid.opts, opts);
+            assert_eq!(
+                grid.cells,
+                hashset![Point(0, 0), Point(1, 1)],
+                "it should not merge cells outside this Grid's bounds"
+            );
+        }
+    }
+
+    mod wrap {
+        use super::*;
+
+        fn cylinder(width: u64) -> GridSettings {
+            GridSettings {
+                width: Some(width),
+                wrap_x: true,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn test_wrap_zero_size() {
+            let grid = Grid::with_settings(vec![Point(3, 0)], cylinder(0));
+            assert_eq!(
+                grid.adjacent_cells(&Point(3, 0)).len(),
+                8,
+                "it should not wrap an axis with no size"
+            );
+        }
+
+        #[test]
+        fn test_adjacent_cells_wrap() {
+            let grid = Grid::with_settings(vec![], cylinder(5));
+            assert_eq!(
+                grid.adjacent_cells(&Point(4, 0)),
+                hashset![
+                    Point(3, -1),
+                    Point(4, -1),
+                    Point(0, -1),
+                    Point(3, 0),
+                    Point(0, 0),
+                    Point(3, 1),
+                    Point(4, 1),
+                    Point(0, 1),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_invert_region_wrap() {
+            let mut grid = Grid::with_settings(vec![Point(1, 0)], cylinder(3));
+            assert_eq!(grid.invert_region((Point(0, 0), Point(3, 0))), 1);
+            assert_eq!(
+                grid,
+                Grid::with_settings(vec![Point(0, 0), Point(2, 0)], cylinder(3)),
+                "it should flip each wrapped cell once"
+            );
+        }
+
+        #[test]
+        fn test_live_neighbors_small_torus() {
+            for size in 1..=3 {
+                let opts = GridSettings {
+                    width: Some(size),
+                    height: Some(size),
+                    wrap_x: true,
+                    wrap_y: true,
+                    ..Default::default()
+                };
+                let size = size as i64;
+                let cells = (0..size)
+                    .flat_map(|y| (0..size).map(move |x| Point(x, y)))
+                    .collect();
+                let grid = Grid::with_settings(cells, opts);
+                let others = (size * size - 1) as usize;
+                for cell in grid.live_cells() {
+                    assert_eq!(
+                        grid.live_neighbors(cell),
+                        others,
+                        "it should count each other cell of a {0}x{0} torus once",
+                        size
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_set_alive_wrap() {
+            let mut grid = Grid::with_settings(vec![], cylinder(5));
+            grid.set_alive(Point(7, -9));
+            assert!(grid.is_alive(&Point(2, -9)));
+            assert!(grid.is_alive(&Point(-3, -9)));
+            assert!(!grid.is_alive(&Point(2, -4)), "the Y axis should not wrap");
+        }
+    }
+
+    mod formats {
+        use super::*;
+
+        #[cfg(feature = "image")]
+        #[test]
+        fn test_from_image() {
+            use std::env;
+            use std::fs;
+
+            let path = env::temp_dir().join(format!("conway-{}.png", std::process::id()));
+            let pixels = [0, 255, 255, 200, 10, 255, 255, 127, 128];
+            image::GrayImage::from_raw(3, 3, pixels.to_vec())
+                .unwrap()
+                .save(&path)
+                .unwrap();
+            let grid = Grid::from_image(&path, 128, Default::default());
+            fs::remove_file(&path).unwrap();
+            assert_eq!(
+                grid.unwrap().cells,
+                hashset![Point(0, 0), Point(1, 1), Point(1, 2)],
+                "it should bring pixels darker than the threshold to life"
+            );
+
+            match Grid::from_image(Path::new("no/such/image.png"), 128, Default::default()) {
+                Err(AppError::IO(_)) => (),
+                result => panic!("expected an IO error, got {:?}", result),
+            }
+        }
+
+        #[test]
+        fn test_from_life106() {
+            let grid = Grid::from_life106("#Life 1.06\n0 -1\n1 0\n-1 1\n  0 1\n1 1\n").unwrap();
+            assert_eq!(
+                grid.cells,
+                hashset![
+                    Point(0, -1),
+                    Point(1, 0),
+                    Point(-1, 1),
+                    Point(0, 1),
+                    Point(1, 1)
+                ]
+            );
+            assert!(Grid::from_life106("#Life 1.06\n0 1 2").is_err());
+            assert!(Grid::from_life106("#Life 1.06\n0").is_err());
+            assert!(Grid::from_life106("#Life 1.06\n0 a").is_err());
+        }
+
+        #[test]
+        fn test_to_life106() {
+            let grid = Grid::new(vec![Point(3, 2), Point(-4, 2), Point(0, -7)]);
+            assert_eq!(grid.to_life106(), "#Life 1.06\n0 -7\n-4 2\n3 2\n");
+        }
+
+        #[test]
+        fn test_to_cells() {
+            let grid = Grid::new(vec![
+                Point(-2, -3),
+                Point(-1, -2),
+                Point(-3, -1),
+                Point(-2, -1),
+            ]);
+            assert_eq!(grid.to_cells(), ".O.\n..O\nOO.\n");
+            assert_eq!(Grid::default().to_cells(), "");
+        }
+
+        #[test]
+        fn test_from_cells() {
+            let grid = Grid::from_cells("!Name: Glider\n.O.\n..O\nOOO\n").unwrap();
+            assert_eq!(grid.to_cells(), ".O.\n..O\nOOO\n");
+            assert!(Grid::from_cells(".x.").is_err());
+        }
+
+        #[test]
+        fn test_from_rle() {
+            let grid =
+                Grid::from_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!").unwrap();
+            assert_eq!(grid.to_cells(), ".O.\n..O\nOOO\n");
+            let grid = Grid::from_rle("x = 1, y = 3\no2$\no!").unwrap();
+            assert_eq!(
+                grid.cells,
+                hashset![Point(0, 0), Point(0, 2)],
+                "it should skip a row for each repeated '$'"
+            );
+            assert!(Grid::from_rle("x = 1, y = 1\nz!").is_err());
+        }
+
+        #[test]
+        fn test_detect_format() {
+            assert_eq!(detect_format("#Life 1.06\n0 0\n"), PatternFormat::Life106);
+            assert_eq!(detect_format("!Name: Blinker\nOOO\n"), PatternFormat::Cells);
+            assert_eq!(detect_format("x = 3, y = 1\n3o!"), PatternFormat::Rle);
+            assert_eq!(
+                detect_format("#C comment\nx=3,y=1\n3o!"),
+                PatternFormat::Rle,
+                "it should look past comments for an RLE header"
+            );
+            assert_eq!(detect_format("# comment\nxxx\n"), PatternFormat::Plaintext);
+            assert_eq!(
+                detect_format("x.x\n.x.\n"),
+                PatternFormat::Plaintext,
+                "ambiguous patterns should default to plaintext"
+            );
+            assert_eq!(detect_format(""), PatternFormat::Plaintext);
+        }
+
+        #[test]
+        fn test_from_reader_detects_format() {
+            let blinker = Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0)]);
+            for pattern in &[
+                "xxx\n",
+                "#Life 1.06\n0 0\n1 0\n2 0\n",
+                "!Name: Blinker\nOOO\n",
+                "x = 3, y = 1\n3o!\n",
+            ] {
+                assert_eq!(
+                    Grid::from_reader(pattern.as_bytes(), Default::default()).unwrap(),
+                    blinker,
+                    "it should parse {:?}",
+                    pattern
+                );
+            }
+        }
+
+        #[test]
+        fn test_life106_round_trip() {
+            let grid = Grid::new(
+                Grid::random(16, 16, 0.4, 1, Default::default())
+                    .live_cells()
+                    .map(|&cell| cell - Point(8, 8))
+                    .collect(),
+            );
+            assert_eq!(Grid::from_life106(&grid.to_life106()).unwrap(), grid);
+        }
+    }
+
+    mod bounded {
+        use super::*;
+
+        fn bounded(width: u64, height: u64) -> GridSettings {
+            GridSettings {
+                width: Some(width),
+                height: Some(height),
+                bounded: true,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn test_in_bounds() {
+            let grid = Grid::with_settings(vec![], bounded(4, 3));
+            assert!(grid.in_bounds(&Point(0, 0)));
+            assert!(grid.in_bounds(&Point(3, 2)));
+            assert!(!grid.in_bounds(&Point(4, 2)));
+            assert!(!grid.in_bounds(&Point(3, 3)));
+            assert!(!grid.in_bounds(&Point(-1, 0)));
+            assert!(Grid::default().in_bounds(&Point(-100, 100)));
+        }
+
+        #[test]
+        fn test_set_alive_bounded() {
+            let mut grid = Grid::with_settings(vec![Point(1, 1), Point(5, 1)], bounded(4, 3));
+            assert_eq!(grid.cells, hashset![Point(1, 1)]);
+            assert!(!grid.set_alive(Point(0, -1)));
+            assert!(!grid.is_alive(&Point(0, -1)));
+        }
+
+        #[test]
+        fn test_adjacent_cells_bounded() {
+            let grid = Grid::with_settings(vec![], bounded(4, 3));
+            assert_eq!(
+                grid.adjacent_cells(&Point(0, 0)),
+                hashset![Point(1, 0), Point(0, 1), Point(1, 1)],
+                "a corner cell should only have 3 neighbors"
+            );
+        }
+    }
+
     mod geometry {
         use super::*;
 
+        #[test]
+        fn test_displacement() {
+            let grid = Grid::new(vec![Point(0, 0), Point(1, 0), Point(1, 1)]);
+            assert_eq!(grid.displacement(&grid), Some(Point(0, 0)));
+            assert_eq!(
+                Grid::new(vec![Point(-4, 2), Point(-3, 2), Point(-3, 3)]).displacement(&grid),
+                Some(Point(-4, 2))
+            );
+            assert_eq!(
+                Grid::new(vec![Point(-4, 2), Point(-3, 2), Point(-4, 3)]).displacement(&grid),
+                None,
+                "grids with different shapes should not match"
+            );
+        }
+
+        #[test]
+        fn test_centroid() {
+            assert_eq!(Grid::new(vec![]).centroid(), Point(0, 0));
+            assert_eq!(
+                Grid::new(vec![Point(-3, 0), Point(0, 1), Point(1, 4)]).centroid(),
+                Point(-1, 1),
+                "it should round toward negative infinity"
+            );
+        }
+
+        #[test]
+        fn test_translated() {
+            let glider = Grid::new(vec![
+                Point(1, 0),
+                Point(2, 1),
+                Point(0, 2),
+                Point(1, 2),
+                Point(2, 2),
+            ]);
+            let moved = glider.translated(Point(10, -5));
+            assert_eq!(moved.population(), glider.population());
+            assert_eq!(
+                moved.cells,
+                hashset![
+                    Point(11, -5),
+                    Point(12, -4),
+                    Point(10, -3),
+                    Point(11, -3),
+                    Point(12, -3)
+                ]
+            );
+
+            let opts = GridSettings {
+                width: Some(4),
+                wrap_x: true,
+                ..Default::default()
+            };
+            let grid = Grid::with_settings(vec![Point(3, 0)], opts.clone());
+            assert_eq!(
+                grid.translated(Point(1, 0)),
+                Grid::with_settings(vec![Point(0, 0)], opts),
+                "it should wrap cells according to the Grid's settings"
+            );
+        }
+
+        #[test]
+        fn test_normalized() {
+            let glider = Grid::new(vec![
+                Point(1, 0),
+                Point(2, 1),
+                Point(0, 2),
+                Point(1, 2),
+                Point(2, 2),
+            ]);
+            let mut shifted: Grid = Default::default();
+            shifted.insert_pattern(&glider, Point(-17, 42));
+            assert_ne!(shifted, glider);
+            assert_eq!(shifted.normalized(), glider.normalized());
+            assert_eq!(
+                shifted.normalized().bounding_box().unwrap().0,
+                Point(0, 0),
+                "it should move the top-left corner to the origin"
+            );
+            assert_ne!(
+                glider.rotated(1).normalized(),
+                glider.normalized(),
+                "different shapes should stay different"
+            );
+        }
+
+        #[test]
+        fn test_find_pattern() {
+            let glider: Grid = ".x.\n..x\nxxx".parse().unwrap();
+            let mut soup = Grid::new(vec![Point(20, 20), Point(21, 20)]);
+            soup.insert_pattern(&glider, Point(-5, 3));
+            soup.insert_pattern(&glider.translated(Point(40, 40)), Point(0, 0));
+            assert_eq!(
+                soup.find_pattern(&glider),
+                vec![Point(-5, 3), Point(40, 40)],
+                "it should find the top-left corner of each glider"
+            );
+
+            soup.set_alive(Point(41, 41));
+            assert_eq!(
+                soup.find_pattern(&glider),
+                vec![Point(-5, 3)],
+                "a glider with extra cells inside its bounds should not match"
+            );
+            assert!(soup.find_pattern(&glider.rotated(1)).is_empty());
+            assert!(soup.find_pattern(&Grid::default()).is_empty());
+        }
+
+        #[test]
+        fn test_diff() {
+            let horizontal = Grid::new(vec![Point(0, 1), Point(1, 1), Point(2, 1)]);
+            let vertical = Grid::new(vec![Point(1, 0), Point(1, 1), Point(1, 2)]);
+            assert_eq!(
+                horizontal.diff(&vertical),
+                (
+                    hashset!(Point(1, 0), Point(1, 2)),
+                    hashset!(Point(0, 1), Point(2, 1))
+                )
+            );
+            assert_eq!(horizontal.count_changes(&vertical), 4);
+            assert_eq!(
+                vertical.diff(&vertical),
+                (HashSet::new(), HashSet::new()),
+                "identical grids should have no differences"
+            );
+        }
+
+        #[test]
+        fn test_rotated() {
+            let glider = Grid::new(vec![
+                Point(1, 0),
+                Point(2, 1),
+                Point(0, 2),
+                Point(1, 2),
+                Point(2, 2),
+            ]);
+            assert_eq!(
+                glider.rotated(1).cells,
+                hashset!(
+                    Point(0, 0),
+                    Point(0, 1),
+                    Point(2, 1),
+                    Point(0, 2),
+                    Point(1, 2)
+                ),
+            );
+            assert_eq!(glider.rotated(2), glider.rotated(1).rotated(1));
+            assert_eq!(
+                glider.rotated(1).rotated(1).rotated(1).rotated(1),
+                glider,
+                "it should return the original cells after four quarter turns"
+            );
+
+            let line = Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0), Point(3, 0)]);
+            assert_eq!(
+                line.rotated(1).rotated(1).rotated(1).rotated(1),
+                line,
+                "it should not drift when the bounds have no center cell"
+            );
+        }
+
+        #[test]
+        fn test_reflected() {
+            let grid = Grid::new(vec![Point(0, 0), Point(1, 0), Point(0, 1), Point(0, 2)]);
+            assert_eq!(
+                grid.reflected(Axis::Horizontal).cells,
+                hashset!(Point(1, 0), Point(0, 0), Point(1, 1), Point(1, 2))
+            );
+            assert_eq!(
+                grid.reflected(Axis::Vertical).cells,
+                hashset!(Point(0, 2), Point(1, 2), Point(0, 1), Point(0, 0))
+            );
+            for &axis in &[
+                Axis::Horizontal,
+                Axis::Vertical,
+                Axis::Diagonal,
+                Axis::AntiDiagonal,
+            ] {
+                assert_eq!(
+                    grid.reflected(axis).reflected(axis),
+                    grid,
+                    "reflecting twice should be the identity"
+                );
+            }
+        }
+
+        #[test]
+        fn test_bounding_box() {
+            assert_eq!(Grid::new(vec![]).bounding_box(), None);
+            assert_eq!(
+                Grid::new(vec![Point(4, -2)]).bounding_box(),
+                Some((Point(4, -2), Point(4, -2)))
+            );
+            assert_eq!(
+                Grid::new(vec![Point(2, 1), Point(-3, 0), Point(-2, 5)]).bounding_box(),
+                Some((Point(-3, 0), Point(2, 5)))
+            );
+        }
+
+        #[test]
+        fn test_symmetries() {
+            let block = Grid::new(vec![Point(0, 0), Point(1, 0), Point(0, 1), Point(1, 1)]);
+            assert_eq!(
+                block.symmetries().len(),
+                6,
+                "a block should be fully symmetric"
+            );
+
+            let glider = Grid::new(vec![
+                Point(1, 0),
+                Point(2, 1),
+                Point(0, 2),
+                Point(1, 2),
+                Point(2, 2),
+            ]);
+            assert_eq!(glider.symmetries(), vec![]);
+
+            let blinker = Grid::new(vec![Point(0, 0), Point(1, 0), Point(2, 0)]);
+            assert_eq!(
+                blinker.symmetries(),
+                vec![
+                    Symmetry::Rotation(2),
+                    Symmetry::Reflection(Axis::Horizontal),
+                    Symmetry::Reflection(Axis::Vertical),
+                ]
+            );
+        }
+
         #[test]
         fn test_calculate_bounds_1() {
             assert_eq!(
@@ -1,15 +1,20 @@
 extern crate conway;
 
-use std::io;
-use std::io::prelude::*;
-
+use conway::ui::termion::TermionUI;
 use conway::Game;
 
 fn main() {
-    let mut game = Game::load().unwrap();
-    let mut stdout = io::stdout();
-    for frame in game.iter() {
-        write!(stdout, "\n{}", frame).unwrap();
-        stdout.flush().unwrap();
+    let game = Game::load().unwrap();
+    if game.settings().analyze {
+        print!("{}", game.analyze());
+    } else {
+        let mut ui = TermionUI::new(game);
+        ui.run().unwrap();
+        if ui.game().settings().dump_cells {
+            print!("{}", ui.game().grid().to_coordinates());
+        }
+        if !ui.game().settings().quiet {
+            eprintln!("{}", ui.game().stats());
+        }
     }
 }
@@ -3,12 +3,72 @@ use std::num::ParseIntError;
 use std::ops;
 use std::str::FromStr;
 
-use super::AppError;
+use super::{AppError, AppResult};
 
 /// A Point is a point on the `Grid`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Point(pub i64, pub i64);
 
+impl Point {
+    /// Parse a Point from a bare pair of coordinates separated by a comma and/or whitespace, such
+    /// as `3 -4` or `3,-4`.
+    pub fn parse_pair(s: &str) -> AppResult<Point> {
+        let mut nums = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|num| !num.is_empty());
+        match (nums.next(), nums.next(), nums.next()) {
+            (Some(x), Some(y), None) => {
+                let parse = |num: &str| {
+                    num.parse()
+                        .map_err(|e: ParseIntError| AppError::ParsePoint(e.to_string()))
+                };
+                Ok(Point(parse(x)?, parse(y)?))
+            }
+            _ => Err(AppError::ParsePoint(format!(
+                "expected a pair of coordinates, got '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Bounds are the rectangle of Points between a top-left and a bottom-right corner, inclusive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds(pub Point, pub Point);
+
+impl Bounds {
+    /// Return the top-left and bottom-right corners.
+    pub fn corners(&self) -> (Point, Point) {
+        (self.0, self.1)
+    }
+
+    /// Return whether the given Point lies within the Bounds, including on their edges.
+    pub fn contains(&self, &Point(x, y): &Point) -> bool {
+        let Bounds(Point(x0, y0), Point(x1, y1)) = *self;
+        x0 <= x && x <= x1 && y0 <= y && y <= y1
+    }
+
+    /// Return whether the Bounds share at least one Point with `other`.
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        let Bounds(Point(x0, y0), Point(x1, y1)) = *self;
+        let Bounds(Point(ox0, oy0), Point(ox1, oy1)) = *other;
+        x0 <= ox1 && ox0 <= x1 && y0 <= oy1 && oy0 <= y1
+    }
+}
+
+impl From<(Point, Point)> for Bounds {
+    fn from((p0, p1): (Point, Point)) -> Self {
+        Bounds(p0, p1)
+    }
+}
+
+impl From<Bounds> for (Point, Point) {
+    fn from(bounds: Bounds) -> Self {
+        bounds.corners()
+    }
+}
+
 impl ops::Add for Point {
     type Output = Self;
 
@@ -93,4 +153,49 @@ mod test {
     fn test_from_str() {
         assert_eq!("(-4, 9)".parse::<Point>().unwrap(), Point(-4, 9));
     }
+
+    #[test]
+    fn test_bounds_contains() {
+        let bounds = Bounds(Point(-1, 2), Point(3, 5));
+        assert!(bounds.contains(&Point(0, 3)));
+        for corner in &[Point(-1, 2), Point(3, 2), Point(-1, 5), Point(3, 5)] {
+            assert!(bounds.contains(corner), "it should contain its corners");
+        }
+        assert!(
+            bounds.contains(&Point(3, 4)) && bounds.contains(&Point(1, 2)),
+            "it should contain its edges"
+        );
+        for outside in &[Point(-2, 3), Point(4, 3), Point(0, 1), Point(0, 6)] {
+            assert!(!bounds.contains(outside));
+        }
+    }
+
+    #[test]
+    fn test_bounds_intersects() {
+        let bounds = Bounds(Point(0, 0), Point(4, 4));
+        assert!(bounds.intersects(&Bounds(Point(2, 2), Point(6, 6))));
+        assert!(bounds.intersects(&Bounds(Point(1, 1), Point(2, 2))));
+        assert!(
+            bounds.intersects(&Bounds(Point(4, 4), Point(8, 8))),
+            "it should count a shared corner as overlapping"
+        );
+        assert!(!bounds.intersects(&Bounds(Point(5, 0), Point(8, 4))));
+        assert!(!bounds.intersects(&Bounds(Point(0, -3), Point(4, -1))));
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(Point::parse_pair("3 -4").unwrap(), Point(3, -4));
+        assert_eq!(Point::parse_pair("3,-4").unwrap(), Point(3, -4));
+        assert_eq!(Point::parse_pair(" 3, -4\t").unwrap(), Point(3, -4));
+        assert!(
+            Point::parse_pair("3").is_err(),
+            "it should require two values"
+        );
+        assert!(Point::parse_pair("3 4 5").is_err());
+        assert!(
+            Point::parse_pair("(3, 4)").is_err(),
+            "it should reject parentheses"
+        );
+    }
 }
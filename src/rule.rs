@@ -0,0 +1,143 @@
+use std::fmt;
+use std::str::FromStr;
+
+use AppError;
+
+/// A Rule decides which cells are born and which survive, given their number of live neighbors.
+/// Rules are written in B/S notation, so Conway's Game of Life is `B3/S23`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Rule {
+    /// Return whether a cell with the given number of live neighbors will be alive next
+    /// generation.
+    pub fn lives(&self, alive: bool, live_neighbors: usize) -> bool {
+        let counts = if alive { &self.survival } else { &self.birth };
+        counts.get(live_neighbors).cloned().unwrap_or(false)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        "B3/S23".parse().unwrap()
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: &[bool; 9]| -> String {
+            (0..9)
+                .filter(|&n| counts[n])
+                .map(|n| n.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(2, '/');
+        let (birth, survival) = match (parts.next(), parts.next()) {
+            (Some(birth), Some(survival)) => (birth, survival),
+            _ => return Err(From::from(format!("expected B.../S..., got '{}'", s))),
+        };
+        let birth = parse_counts(birth, 'B')?;
+        // Only cells next to living ones are ever evaluated, so births with no neighbors at all
+        // would never happen.
+        if birth[0] {
+            return Err(From::from("B0 rules are not supported".to_owned()));
+        }
+        Ok(Rule {
+            birth,
+            survival: parse_counts(survival, 'S')?,
+        })
+    }
+}
+
+/// Parse the neighbor counts in one half of a rule, which must start with `marker`.
+fn parse_counts(s: &str, marker: char) -> Result<[bool; 9], AppError> {
+    let mut chars = s.chars();
+    if chars.next().map(|c| c.to_ascii_uppercase()) != Some(marker) {
+        return Err(From::from(format!(
+            "expected '{}' at the start of '{}'",
+            marker, s
+        )));
+    }
+    let mut counts = [false; 9];
+    for c in chars {
+        let n = match c.to_digit(10) {
+            Some(n) if n <= 8 => n as usize,
+            _ => {
+                return Err(From::from(format!(
+                    "'{}' is not a neighbor count from 0-8",
+                    c
+                )))
+            }
+        };
+        if counts[n] {
+            return Err(From::from(format!(
+                "'{}' appears more than once in '{}'",
+                c, s
+            )));
+        }
+        counts[n] = true;
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_error(s: &str) -> String {
+        match s.parse::<Rule>() {
+            Err(AppError::Msg(msg)) => msg,
+            result => panic!("expected an error parsing '{}', got {:?}", s, result),
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        let life: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(life, Default::default());
+        assert!(life.lives(false, 3));
+        assert!(!life.lives(false, 2));
+        assert!(life.lives(true, 2));
+        assert!(!life.lives(true, 4));
+
+        let high_life: Rule = "B36/S23".parse().unwrap();
+        assert!(high_life.lives(false, 6));
+        assert_eq!(high_life.to_string(), "B36/S23");
+
+        let rule: Rule = "b2/s".parse().unwrap();
+        assert_eq!(rule.to_string(), "B2/S", "it should allow empty counts");
+        assert!(!rule.lives(true, 2));
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!(
+            parse_error("B9/S23"),
+            "'9' is not a neighbor count from 0-8"
+        );
+        assert_eq!(parse_error("S23"), "expected B.../S..., got 'S23'");
+        assert_eq!(parse_error("B3S23"), "expected B.../S..., got 'B3S23'");
+        assert_eq!(parse_error("S23/B3"), "expected 'B' at the start of 'S23'");
+        assert_eq!(
+            parse_error("B33/S23"),
+            "'3' appears more than once in 'B33'"
+        );
+        assert_eq!(parse_error("B03/S23"), "B0 rules are not supported");
+        assert!(
+            "B3/S0".parse::<Rule>().is_ok(),
+            "S0 should still be allowed"
+        );
+    }
+}
@@ -3,7 +3,7 @@ pub mod termion;
 use num_integer::div_floor;
 
 /// A Rect is a tuple struct containing the (x-origin, y-origin, width, height) of a rectangle.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Rect {
     x0: u16,
     y0: u16,
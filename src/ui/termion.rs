@@ -1,17 +1,20 @@
+use std::fs::File;
 use std::io;
 use std::io::prelude::*;
-use std::thread;
 
 use std::cmp;
 use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use termion::event::Key;
-use termion::input::TermRead;
+use termion::event::{Event, Key, MouseButton, MouseEvent};
+use termion::input::{MouseTerminal, TermRead};
 use termion::raw::IntoRawMode;
-use termion::{clear, cursor, style};
+use termion::{async_stdin, clear, color, cursor, style};
 
 use super::Rect;
-use {AppResult, Game};
+use config;
+use {AppResult, Bounds, Game, Grid, Point, Settings};
 
 pub enum Sym {
     BoxTopLeft,
@@ -107,14 +110,72 @@ pub trait Widget {
 static MENU_CMDS: &'static str = "
 %     COMMANDS     %
 --------------------
+pause   -->        p
 next    -->    Space
+back    -->     Bksp
+reset   -->        r
+faster  -->        +
+slower  -->        -
+zoom    -->      [/]
+brush   -->        b
 quit    -->    q/Esc
 ";
 
+/// How long to wait between polls for input in interactive mode.
+const POLL_INTERVAL_MS: u64 = 10;
+
+/// The smallest width or height of the Board, including its border.
+const MIN_BOARD_SIZE: u16 = 5;
+
+/// Bounds for the tick delay when changing speed at runtime.
+const MIN_DELAY_MS: u64 = 10;
+const MAX_DELAY_MS: u64 = 5000;
+
+/// The largest brush for painting cells with the mouse. Brush sizes cycle back to 1 after it.
+const MAX_BRUSH_SIZE: u16 = 5;
+
+/// Halve the delay if `faster` is true, otherwise double it, keeping it within sane bounds.
+pub fn adjust_delay(delay: Duration, faster: bool) -> Duration {
+    let delay = if faster { delay / 2 } else { delay * 2 };
+    cmp::min(
+        cmp::max(delay, Duration::from_millis(MIN_DELAY_MS)),
+        Duration::from_millis(MAX_DELAY_MS),
+    )
+}
+
+/// Format a labelled value to line up with the commands in the Menu.
+fn menu_line<T: fmt::Display>(label: &str, value: T) -> String {
+    format!("{:<8}-->{:>9}", label, value)
+}
+
+/// Return the cells covered by a `size` by `size` brush centered on `center`. Brushes of an even
+/// size extend further to the bottom right.
+pub fn brush_cells(center: Point, size: u16) -> Vec<Point> {
+    let size = i64::from(size);
+    let top_left = center - Point((size - 1) / 2, (size - 1) / 2);
+    (0..size)
+        .flat_map(|dy| (0..size).map(move |dx| top_left + Point(dx, dy)))
+        .collect()
+}
+
+/// Format the current generation and population for display in the Menu.
+pub fn format_stats(generation: u64, population: usize) -> String {
+    format!(
+        "{}\n{}",
+        menu_line("gen", generation),
+        menu_line("pop", population)
+    )
+}
+
 pub struct Menu {
     rect: Rect,
     padding: u16,
     margin: u16,
+    delay: Duration,
+    paused: bool,
+    brush_size: u16,
+    generation: u64,
+    population: usize,
 }
 
 impl Menu {
@@ -123,6 +184,11 @@ impl Menu {
             rect,
             padding,
             margin,
+            delay: Default::default(),
+            paused: false,
+            brush_size: 1,
+            generation: 0,
+            population: 0,
         }
     }
 }
@@ -141,88 +207,883 @@ impl Widget for Menu {
     }
 
     fn draw(&self) -> String {
-        MENU_CMDS.trim().to_string()
+        format!(
+            "{}\n\n{}\n\n{}\n{}\n{}",
+            MENU_CMDS.trim(),
+            format_stats(self.generation, self.population),
+            menu_line("delay", format!("{}ms", self.delay.as_millis())),
+            menu_line("state", if self.paused { "paused" } else { "running" }),
+            menu_line("brush", format!("{0}x{0}", self.brush_size)),
+        )
     }
 }
 
-impl Widget for Game {
+/// Return the cells within `viewport` that changed between `prev` and `next`, each paired with the
+/// character it should now be drawn as.
+pub fn draw_commands(
+    prev: &Grid,
+    next: &Grid,
+    viewport: Bounds,
+    opts: &Settings,
+) -> Vec<(Point, char)> {
+    let in_view = |cell: &Point| viewport.contains(cell);
+    let (char_alive, char_dead) = opts.cell_chars();
+    let (born, died) = prev.diff(next);
+    born.into_iter()
+        .filter(&in_view)
+        .map(|cell| (cell, char_alive))
+        .chain(
+            died.into_iter()
+                .filter(&in_view)
+                .map(|cell| (cell, char_dead)),
+        )
+        .collect()
+}
+
+/// Translate a position on the terminal into the cell drawn there by a Board occupying `rect` and
+/// showing `viewport`. Return None if no cell is drawn at that position. With `halfblock`, each row
+/// of the terminal shows two rows of cells, and the upper one is returned.
+pub fn screen_to_cell(
+    rect: &Rect,
+    viewport: Bounds,
+    zoom: u16,
+    halfblock: bool,
+    (x, y): (u16, u16),
+) -> Option<Point> {
+    let (x0, y0, width, height) = rect.shape();
+    // Cells are drawn inside the Board's border, and terminal positions start at 1.
+    let (x0, y0) = (x0 + 2, y0 + 2);
+    if x < x0 || y < y0 || x - x0 >= width - 2 || y - y0 >= height - 2 {
+        return None;
+    }
+    let Point(vx0, vy0) = viewport.0;
+    let (col, row) = ((x - x0) / zoom, (y - y0) / zoom);
+    let row = if halfblock { 2 } else { 1 } * i64::from(row);
+    let cell = Point(vx0 + col as i64, vy0 + row);
+    if viewport.contains(&cell) {
+        Some(cell)
+    } else {
+        None
+    }
+}
+
+/// Scale a drawn frame so that each cell becomes a `zoom` by `zoom` block. Any escape codes that
+/// precede a cell are repeated along with it.
+pub fn zoom_frame(frame: &str, zoom: u16) -> String {
+    let mut output = String::new();
+    for line in frame.lines() {
+        let mut scaled = String::new();
+        let mut cell = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            cell.push(c);
+            if c == '\x1b' {
+                for c in chars.by_ref() {
+                    cell.push(c);
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                scaled.push_str(&cell.repeat(zoom as usize));
+                cell.clear();
+            }
+        }
+        scaled.push_str(&cell);
+        for _ in 0..zoom {
+            output.push_str(&scaled);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Surround a drawn frame with a box.
+pub fn draw_border(frame: &str) -> String {
+    // Escape codes take up no space on screen, so they're left out of each line's width.
+    let width = |line: &str| {
+        let mut chars = line.chars();
+        let mut width = 0;
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                width += 1;
+            }
+        }
+        width
+    };
+    let inner_width = frame.lines().map(width).max().unwrap_or(0);
+    let horizontal = Sym::BoxHorizontal.to_string().repeat(inner_width);
+
+    let mut output = format!("{}{}{}\n", Sym::BoxTopLeft, horizontal, Sym::BoxTopRight);
+    for line in frame.lines() {
+        output.push_str(&format!(
+            "{}{}{}{}\n",
+            Sym::BoxVertical,
+            line,
+            " ".repeat(inner_width - width(line)),
+            Sym::BoxVertical
+        ));
+    }
+    output.push_str(&format!(
+        "{}{}{}\n",
+        Sym::BoxBottomLeft,
+        horizontal,
+        Sym::BoxBottomRight
+    ));
+    output
+}
+
+/// Return the largest zoom at which the whole viewport still fits inside a Board occupying `rect`.
+pub fn max_zoom(rect: &Rect, Bounds(Point(x0, y0), Point(x1, y1)): Bounds) -> u16 {
+    let (_, _, width, height) = rect.shape();
+    let (cols, rows) = ((x1 - x0 + 1) as u16, (y1 - y0 + 1) as u16);
+    cmp::max(1, cmp::min((width - 2) / cols, (height - 2) / rows))
+}
+
+/// Return the Rect the Board should fill in a terminal of the given size, to the right of the
+/// Menu if there is one.
+pub fn board_rect(menu: Option<&Rect>, (cols, rows): (u16, u16)) -> Rect {
+    let (x0, y0) = match menu.map(Rect::shape) {
+        Some((x0, y0, width, _)) => (x0 + width - 1, y0),
+        None => (0, 0),
+    };
+    Rect::new(
+        x0,
+        y0,
+        cmp::max(cols.saturating_sub(x0), MIN_BOARD_SIZE),
+        cmp::max(rows.saturating_sub(y0), MIN_BOARD_SIZE),
+    )
+}
+
+/// Board is the Widget that displays a Game.
+pub struct Board<'a> {
+    game: &'a Game,
+    rect: &'a Rect,
+    zoom: u16,
+}
+
+impl<'a> Widget for Board<'a> {
     fn rect(&self) -> &Rect {
-        &self.rect
+        self.rect
     }
 
     fn draw(&self) -> String {
-        self.grid.to_string()
+        if self.zoom > 1 {
+            zoom_frame(&self.game.draw(), self.zoom)
+        } else {
+            self.game.draw()
+        }
+    }
+}
+
+/// Write the escape codes that return the terminal to a usable state: cleared, with default
+/// colors and styles and a visible cursor at the top left.
+fn reset_terminal<W: Write>(out: &mut W) -> io::Result<()> {
+    write!(
+        out,
+        "{}{}{}{}{}",
+        clear::All,
+        color::Fg(color::Reset),
+        style::Reset,
+        cursor::Goto(1, 1),
+        cursor::Show,
+    )?;
+    out.flush()
+}
+
+/// TerminalGuard wraps the terminal's output and resets the terminal when dropped, so that it's
+/// restored however the UI exits, including by an error or a panic. Raw mode delivers Ctrl-C as a
+/// key press rather than SIGINT, so quitting that way also ends up here.
+pub struct TerminalGuard<W: Write>(W);
+
+impl<W: Write> TerminalGuard<W> {
+    pub fn new(out: W) -> Self {
+        TerminalGuard(out)
+    }
+}
+
+impl<W: Write> Write for TerminalGuard<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<W: Write> Drop for TerminalGuard<W> {
+    fn drop(&mut self) {
+        // There's nowhere left to report a failure to, so it's ignored.
+        let _ = reset_terminal(&mut self.0);
     }
 }
 
 pub struct TermionUI {
     game: Game,
+    rect: Rect,
     menu: Menu,
+    delay: Duration,
+    paused: bool,
+    zoom: u16,
+    brush_size: u16,
+    prev_frame: Option<(Grid, Bounds)>,
 }
 
 impl TermionUI {
-    pub fn new(mut game: Game) -> TermionUI {
-        let menu = Menu::new(Rect::new(0, 0, 23, 20), 1, 1);
-        game.rect = {
+    pub fn new(game: Game) -> TermionUI {
+        let menu = Menu::new(Rect::new(0, 0, 23, 22), 1, 1);
+        let rect = if game.settings().no_menu {
+            Rect::new(0, 0, 40, 22)
+        } else {
             let (x0, y0, width, height) = menu.rect().shape();
             Rect::new(x0 + width - 1, y0, 40, height)
         };
-        TermionUI { game, menu }
+        let delay = game.settings().delay;
+        TermionUI {
+            game,
+            rect,
+            menu,
+            delay,
+            paused: false,
+            zoom: 1,
+            brush_size: 1,
+            prev_frame: None,
+        }
+    }
+
+    // Resize the Board to fill the terminal, returning whether its size changed. If the viewport
+    // has no fixed size, it's resized to fill the Board.
+    fn fit_to_terminal(&mut self) -> AppResult<bool> {
+        let menu = if self.game.settings().no_menu {
+            None
+        } else {
+            Some(self.menu.rect())
+        };
+        let rect = board_rect(menu, termion::terminal_size()?);
+        if rect == self.rect {
+            return Ok(false);
+        }
+        self.rect = rect;
+        self.prev_frame = None;
+        let (_, _, width, height) = rect.shape();
+        let opts = self.game.settings();
+        let width = opts.width.unwrap_or(u64::from(width - 2));
+        // Half blocks pack two rows of cells into each row of the terminal.
+        let rows = if opts.halfblock { 2 } else { 1 } * u64::from(height - 2);
+        let height = opts.height.unwrap_or(rows);
+        self.game.resize_viewport(width, height);
+        Ok(true)
+    }
+
+    pub fn render<W: Write>(&mut self, stdout: &mut W) -> AppResult<()> {
+        self.menu.delay = self.delay;
+        self.menu.paused = self.paused;
+        self.menu.brush_size = self.brush_size;
+        self.menu.generation = self.game.generation();
+        self.menu.population = self.game.population();
+
+        let viewport = self.game.viewport();
+        self.zoom = cmp::min(self.zoom, max_zoom(&self.rect, viewport));
+        let opts = self.game.settings();
+        let show_menu = !opts.no_menu;
+        // Only cells that changed need redrawing, unless the view moved or every cell could look
+        // different from one frame to the next.
+        let partial = !opts.show_age
+            && !opts.debug_neighbors
+            && !opts.trace
+            && !opts.halfblock
+            && !opts.grid.wrap_x
+            && !opts.grid.wrap_y;
+        match self.prev_frame.take() {
+            Some((ref grid, prev_viewport)) if partial && prev_viewport == viewport => {
+                if show_menu {
+                    self.menu.render(stdout)?;
+                }
+                self.render_changes(stdout, grid, viewport)?;
+            }
+            _ => {
+                write!(stdout, "{}{}", clear::All, cursor::Hide)?;
+                if show_menu {
+                    self.menu.render(stdout)?;
+                }
+                Board {
+                    game: &self.game,
+                    rect: &self.rect,
+                    zoom: self.zoom,
+                }
+                .render(stdout)?;
+            }
+        }
+        self.prev_frame = Some((self.game.grid().clone(), viewport));
+        Ok(())
     }
 
-    pub fn render(&mut self, stdout: &mut io::StdoutLock) -> AppResult<()> {
-        self.menu.render(stdout)?;
-        self.game.render(stdout)?;
+    // Redraw only the cells in the Board that changed since `prev` was drawn.
+    fn render_changes<W: Write>(
+        &self,
+        out: &mut W,
+        prev: &Grid,
+        viewport: Bounds,
+    ) -> AppResult<()> {
+        let opts = self.game.settings();
+        let color = opts
+            .color_alive
+            .as_ref()
+            .and_then(|c| config::color_code(c));
+        let (x0, y0, _, height) = self.rect.shape();
+        let Point(vx0, vy0) = viewport.0;
+        let zoom = self.zoom;
+
+        for (Point(x, y), c) in draw_commands(prev, self.game.grid(), viewport, opts) {
+            let (col, row) = ((x - vx0) as u16 * zoom, (y - vy0) as u16 * zoom);
+            let block = c.to_string().repeat(zoom as usize);
+            for dy in 0..zoom {
+                if row + dy >= height - 2 {
+                    break;
+                }
+                write!(out, "{}", cursor::Goto(x0 + 2 + col, y0 + 2 + row + dy))?;
+                match color {
+                    Some(ref code) if c == opts.char_alive => {
+                        write!(out, "{}{}{}", code, block, color::Fg(color::Reset))?
+                    }
+                    _ => write!(out, "{}", block)?,
+                }
+            }
+        }
         Ok(())
     }
 
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
     pub fn run(&mut self) -> AppResult<()> {
-        if self.game.opts.raw_mode {
-            self.run_as_stream()
-        } else {
+        if self.game.settings().interactive {
             self.run_as_app()
+        } else {
+            self.run_as_stream()
         }
     }
 
+    /// Run the Game for the given number of generations without rendering or waiting between
+    /// them, for profiling the simulation on its own.
+    pub fn run_headless(&mut self, generations: u64) -> AppResult<()> {
+        self.game.tick_n(generations);
+        Ok(())
+    }
+
     pub fn run_as_app(&mut self) -> AppResult<()> {
-        let stdout = io::stdout().into_raw_mode()?;
-        let mut stdout = stdout.lock();
+        let mut stdout = TerminalGuard::new(MouseTerminal::from(io::stdout().into_raw_mode()?));
 
-        'Outer: while !self.game.is_over() {
-            write!(stdout, "{}{}", clear::All, cursor::Hide)?;
+        let mut events = async_stdin().events();
+        let mut last_tick = Instant::now();
+        self.fit_to_terminal()?;
 
+        'Outer: while !self.game.is_over() {
             self.render(&mut stdout)?;
             stdout.flush()?;
 
-            for c in io::stdin().keys() {
-                match c? {
-                    Key::Char('q') | Key::Esc | Key::Ctrl('c') => break 'Outer,
-                    Key::Char(' ') => break,
-                    _ => (),
+            loop {
+                match events.next() {
+                    Some(event) => match event? {
+                        Event::Key(Key::Char('q'))
+                        | Event::Key(Key::Esc)
+                        | Event::Key(Key::Ctrl('c')) => break 'Outer,
+                        Event::Key(Key::Char('p')) => {
+                            self.paused = !self.paused;
+                            last_tick = Instant::now();
+                            break;
+                        }
+                        Event::Key(Key::Char(' ')) => {
+                            self.game.tick();
+                            break;
+                        }
+                        Event::Key(Key::Backspace) if self.game.step_back() => break,
+                        Event::Key(Key::Char('r')) => {
+                            self.game.reset();
+                            self.prev_frame = None;
+                            break;
+                        }
+                        Event::Key(Key::Char('+')) => {
+                            self.delay = adjust_delay(self.delay, true);
+                            break;
+                        }
+                        Event::Key(Key::Char('-')) => {
+                            self.delay = adjust_delay(self.delay, false);
+                            break;
+                        }
+                        Event::Key(Key::Char(']')) => {
+                            self.zoom += 1;
+                            self.prev_frame = None;
+                            break;
+                        }
+                        Event::Key(Key::Char('b')) => {
+                            self.brush_size = self.brush_size % MAX_BRUSH_SIZE + 1;
+                            break;
+                        }
+                        Event::Key(Key::Char('[')) if self.zoom > 1 => {
+                            self.zoom -= 1;
+                            self.prev_frame = None;
+                            break;
+                        }
+                        Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y)) => {
+                            let viewport = self.game.viewport();
+                            let zoom = self.zoom;
+                            let halfblock = self.game.settings().halfblock;
+                            let pos = (x, y);
+                            if let Some(cell) =
+                                screen_to_cell(&self.rect, viewport, zoom, halfblock, pos)
+                            {
+                                for cell in brush_cells(cell, self.brush_size) {
+                                    self.game.toggle_cell(cell);
+                                }
+                                break;
+                            }
+                        }
+                        _ => (),
+                    },
+                    None if !self.paused && last_tick.elapsed() >= self.delay => {
+                        self.game.tick();
+                        last_tick = Instant::now();
+                        break;
+                    }
+                    None if self.fit_to_terminal()? => break,
+                    None => thread::sleep(Duration::from_millis(POLL_INTERVAL_MS)),
                 }
             }
-
-            self.game.tick();
         }
-        self.teardown(&mut stdout)
+        Ok(())
     }
 
     pub fn run_as_stream(&mut self) -> AppResult<()> {
-        let mut stdout = io::stdout();
-        while !self.game.is_over() {
-            for line in self.game.draw().lines() {
-                write!(stdout, "{}\n", line)?;
-            }
-            write!(stdout, "\n")?;
-            stdout.flush()?;
-            self.game.tick();
-            thread::sleep(self.game.opts.tick_delay);
+        match self.game.settings().output.clone() {
+            Some(path) => self.write_stream(&mut File::create(path)?),
+            None => self.write_stream(&mut io::stdout()),
+        }
+    }
+
+    fn write_stream<W: Write>(&mut self, out: &mut W) -> AppResult<()> {
+        if let Some(generations) = self.game.settings().step {
+            self.write_step(out, generations)
+        } else if self.game.settings().count_only {
+            self.write_counts(out)
+        } else {
+            self.write_frames(out)
         }
+    }
+
+    /// Advance the Game by the given number of generations, then write only the final frame to
+    /// `out`.
+    pub fn write_step<W: Write>(&mut self, out: &mut W, generations: u64) -> AppResult<()> {
+        self.game.tick_n(generations);
+        write!(out, "{}", self.game.draw())?;
+        out.flush()?;
         Ok(())
     }
 
-    pub fn teardown<W: Write>(&self, mut out: W) -> AppResult<()> {
-        write!(out, "{}{}{}", clear::All, style::Reset, cursor::Goto(1, 1),)?;
+    /// Write the generation and population of each generation of the Game to `out`, one
+    /// generation per line, starting with the current one.
+    pub fn write_counts<W: Write>(&mut self, out: &mut W) -> AppResult<()> {
+        writeln!(out, "{} {}", self.game.generation(), self.game.population())?;
+        let start = self.game.generation() + 1;
+        for (generation, grid) in (start..).zip(self.game.grids()) {
+            writeln!(out, "{} {}", generation, grid.population())?;
+        }
+        out.flush()?;
         Ok(())
     }
+
+    /// Write each frame of the Game to `out`, preceded by a line containing the frame separator,
+    /// if there is one.
+    pub fn write_frames<W: Write>(&mut self, out: &mut W) -> AppResult<()> {
+        let border = self.game.settings().border;
+        let delay = self.game.settings().delay;
+        let separator = self.game.settings().frame_separator.clone();
+        let quiet = self.game.settings().quiet;
+        for (i, frame) in self.game.iter().enumerate() {
+            let frame = if border { draw_border(&frame) } else { frame };
+            if quiet {
+                // Quiet frames are only separated from each other, by a single blank line.
+                if i > 0 {
+                    writeln!(out)?;
+                }
+            } else if let Some(ref separator) = separator {
+                writeln!(out, "{}", separator)?;
+            }
+            write!(out, "{}", frame)?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use config::ConfigReader;
+    use std::env;
+    use std::fs;
+    use {Settings, View};
+
+    #[test]
+    fn test_adjust_delay() {
+        let delay = Duration::from_millis(500);
+        assert_eq!(
+            adjust_delay(delay, true),
+            Duration::from_millis(250),
+            "it should halve the delay when going faster"
+        );
+        assert_eq!(
+            adjust_delay(delay, false),
+            Duration::from_millis(1000),
+            "it should double the delay when going slower"
+        );
+        assert_eq!(
+            adjust_delay(Duration::from_millis(15), true),
+            Duration::from_millis(MIN_DELAY_MS),
+            "it should not go below the minimum delay"
+        );
+        assert_eq!(
+            adjust_delay(Duration::from_millis(4000), false),
+            Duration::from_millis(MAX_DELAY_MS),
+            "it should not go above the maximum delay"
+        );
+    }
+
+    #[test]
+    fn test_format_stats() {
+        assert_eq!(
+            format_stats(12, 305),
+            "gen     -->       12\npop     -->      305"
+        );
+    }
+
+    #[test]
+    fn test_screen_to_cell() {
+        let rect = Rect::new(22, 0, 40, 20);
+        let mut game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                view: View::Fixed,
+                width: Some(9),
+                height: Some(9),
+                ..Default::default()
+            },
+        );
+        game.scroll(3, -2);
+        let viewport = game.viewport();
+        assert_eq!(viewport.0, Point(-3, 2));
+
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, false, (24, 2)),
+            Some(Point(-3, 2))
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, false, (29, 7)),
+            Some(Point(2, 7))
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, false, (23, 2)),
+            None,
+            "it should ignore the Board's border"
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, false, (5, 5)),
+            None,
+            "it should ignore positions outside the Board"
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, false, (40, 5)),
+            None,
+            "it should ignore positions past the edge of the viewport"
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 2, false, (29, 7)),
+            Some(Point(-1, 4)),
+            "it should account for zoom"
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, true, (29, 5)),
+            Some(Point(2, 8)),
+            "it should show two rows of cells per row of the terminal with half blocks"
+        );
+        assert_eq!(
+            screen_to_cell(&rect, viewport, 1, true, (29, 7)),
+            None,
+            "it should ignore half block rows past the edge of the viewport"
+        );
+    }
+
+    #[test]
+    fn test_zoom_frame() {
+        assert_eq!(zoom_frame("#-\n-#\n", 1), "#-\n-#\n");
+        assert_eq!(
+            zoom_frame("#-\n-#\n", 2),
+            "##--\n##--\n--##\n--##\n",
+            "it should double each row and column"
+        );
+
+        let code = config::color_code("red").unwrap();
+        let reset = color::Fg(color::Reset).to_string();
+        assert_eq!(
+            zoom_frame(&format!("{}#{}-\n", code, reset), 2),
+            format!("{0}#{0}#{1}-{1}-\n", code, reset).repeat(2),
+            "it should keep escape codes with their cells"
+        );
+    }
+
+    #[test]
+    fn test_draw_border() {
+        let frame = draw_border("#--\n-#-\n");
+        let lines: Vec<&str> = frame.lines().collect();
+        assert_eq!(lines, vec!["╔═══╗", "║#--║", "║-#-║", "╚═══╝"]);
+
+        let code = config::color_code("red").unwrap();
+        let frame = draw_border(&format!("{}#{}-\n", code, color::Fg(color::Reset)));
+        assert_eq!(
+            frame.lines().next(),
+            Some("╔══╗"),
+            "it should not count escape codes towards the width"
+        );
+    }
+
+    #[test]
+    fn test_max_zoom() {
+        let rect = Rect::new(22, 0, 40, 20);
+        assert_eq!(max_zoom(&rect, Bounds(Point(0, 0), Point(9, 5))), 3);
+        assert_eq!(max_zoom(&rect, Bounds(Point(0, 0), Point(99, 5))), 1);
+    }
+
+    #[test]
+    fn test_brush_cells() {
+        let mut cells = brush_cells(Point(5, 5), 3);
+        cells.sort_by_key(|&Point(x, y)| (y, x));
+        assert_eq!(
+            cells,
+            vec![
+                Point(4, 4),
+                Point(5, 4),
+                Point(6, 4),
+                Point(4, 5),
+                Point(5, 5),
+                Point(6, 5),
+                Point(4, 6),
+                Point(5, 6),
+                Point(6, 6),
+            ]
+        );
+        assert_eq!(brush_cells(Point(5, 5), 1), vec![Point(5, 5)]);
+        assert_eq!(
+            brush_cells(Point(5, 5), 2),
+            vec![Point(5, 5), Point(6, 5), Point(5, 6), Point(6, 6)],
+            "an even brush should extend to the bottom right"
+        );
+    }
+
+    #[test]
+    fn test_terminal_guard() {
+        let mut out = Vec::new();
+        {
+            let mut guard = TerminalGuard::new(&mut out);
+            write!(guard, "frame").unwrap();
+        }
+        let mut expected = b"frame".to_vec();
+        reset_terminal(&mut expected).unwrap();
+        assert_eq!(out, expected, "it should reset the terminal when dropped");
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with(&cursor::Show.to_string()));
+    }
+
+    #[test]
+    fn test_board_rect() {
+        let menu = Rect::new(0, 0, 23, 20);
+        assert_eq!(board_rect(Some(&menu), (80, 24)), Rect::new(22, 0, 58, 24));
+        assert_eq!(
+            board_rect(None, (80, 24)),
+            Rect::new(0, 0, 80, 24),
+            "it should span the full width without a menu"
+        );
+        assert_eq!(
+            board_rect(Some(&menu), (20, 3)),
+            Rect::new(22, 0, MIN_BOARD_SIZE, MIN_BOARD_SIZE),
+            "it should not shrink below the minimum size"
+        );
+    }
+
+    #[test]
+    fn test_draw_commands() {
+        let opts: Settings = Default::default();
+        let horizontal = Grid::new(vec![Point(0, 1), Point(1, 1), Point(2, 1)]);
+        let vertical = Grid::new(vec![Point(1, 0), Point(1, 1), Point(1, 2)]);
+
+        let mut commands = draw_commands(
+            &horizontal,
+            &vertical,
+            Bounds(Point(0, 0), Point(2, 2)),
+            &opts,
+        );
+        commands.sort_by_key(|&(Point(x, y), _)| (y, x));
+        assert_eq!(
+            commands,
+            vec![
+                (Point(1, 0), opts.char_alive),
+                (Point(0, 1), opts.char_dead),
+                (Point(2, 1), opts.char_dead),
+                (Point(1, 2), opts.char_alive),
+            ],
+            "it should only draw cells that changed"
+        );
+
+        assert_eq!(
+            draw_commands(
+                &horizontal,
+                &vertical,
+                Bounds(Point(0, 0), Point(1, 0)),
+                &opts
+            ),
+            vec![(Point(1, 0), opts.char_alive)],
+            "it should skip cells outside of the viewport"
+        );
+    }
+
+    #[test]
+    fn test_run_as_stream_to_file() {
+        let path = env::temp_dir().join(format!("conway-frames-{}.txt", std::process::id()));
+        let game = Game::new(
+            "xxx".parse().unwrap(),
+            Settings {
+                delay: Duration::from_millis(0),
+                max_generations: Some(4),
+                output: Some(path.clone()),
+                frame_separator: Some(config::FRAME_RULE.to_string()),
+                ..Default::default()
+            },
+        );
+        TermionUI::new(game).run_as_stream().unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            contents
+                .lines()
+                .filter(|&line| line == config::FRAME_RULE)
+                .count(),
+            4,
+            "it should write one frame per generation"
+        );
+    }
+
+    #[test]
+    fn test_run_headless() {
+        let game = Game::new(
+            ".x.\n..x\nxxx".parse().unwrap(),
+            Settings {
+                delay: Duration::from_secs(60),
+                ..Default::default()
+            },
+        );
+        let mut ui = TermionUI::new(game);
+        ui.run_headless(25).unwrap();
+        assert_eq!(ui.game().generation(), 25);
+        ui.run_headless(0).unwrap();
+        assert_eq!(ui.game().generation(), 25);
+    }
+
+    #[test]
+    fn test_write_step() {
+        let args = "conway --pattern xxx --step 1 -v locked -w 3 -h 3".split(' ');
+        let ConfigReader { settings, grid } = ConfigReader::from_args(args).unwrap();
+        let mut out = Vec::new();
+        TermionUI::new(Game::new(grid, settings))
+            .write_stream(&mut out)
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "-#-\n-#-\n-#-\n");
+    }
+
+    #[test]
+    fn test_write_counts() {
+        let game = Game::new(
+            ".x.\n.x.\n.x.".parse().unwrap(),
+            Settings {
+                max_generations: Some(4),
+                ..Default::default()
+            },
+        );
+        let mut out = Vec::new();
+        TermionUI::new(game).write_counts(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "0 3\n1 3\n2 3\n3 3\n4 3\n");
+
+        let game = Game::new("x".parse().unwrap(), Default::default());
+        let mut out = Vec::new();
+        TermionUI::new(game).write_counts(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "0 1\n1 0\n",
+            "it should stop once the game is over"
+        );
+    }
+
+    #[test]
+    fn test_write_frames_separator() {
+        let frames = |separator: Option<&str>| {
+            let game = Game::new(
+                "xx\nxx".parse().unwrap(),
+                Settings {
+                    delay: Duration::from_millis(0),
+                    max_generations: Some(2),
+                    view: View::Fixed,
+                    width: Some(2),
+                    height: Some(2),
+                    frame_separator: separator.map(String::from),
+                    ..Default::default()
+                },
+            );
+            let mut out = Vec::new();
+            TermionUI::new(game).write_frames(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            frames(Some("===")),
+            "===\n##-\n##-\n---\n===\n##-\n##-\n---\n"
+        );
+        assert_eq!(
+            frames(None),
+            "##-\n##-\n---\n##-\n##-\n---\n",
+            "it should write frames back to back with no separator"
+        );
+    }
+
+    #[test]
+    fn test_write_frames_quiet() {
+        let config = ConfigReader::from_args(vec![
+            "conway",
+            "--pattern",
+            "xxx",
+            "-v",
+            "fixed",
+            "--width",
+            "2",
+            "--height",
+            "2",
+            "--max-gens",
+            "2",
+            "--delay",
+            "0",
+            "--quiet",
+        ])
+        .unwrap();
+        let mut ui = TermionUI::new(Game::new(config.grid, config.settings));
+        let mut out = Vec::new();
+        ui.write_frames(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "-#-\n-#-\n---\n\n###\n---\n---\n",
+            "it should write only the frames, with one blank line between them"
+        );
+    }
 }